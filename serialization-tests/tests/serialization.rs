@@ -452,6 +452,89 @@ fn bincode_stablegraph_to_graph_i32_1() {
     assert_eq!(g2[node_index(0)], x);
 }
 
+#[test]
+fn json_graphmap_self_loops() {
+    let mut g1 = DiGraphMap::new();
+    g1.add_edge(1, 2, 'x');
+    g1.add_edge(2, 1, 'y');
+    g1.add_edge(2, 2, 'z');
+    g1.add_node(3);
+    let g2: DiGraphMap<i32, char> = rejson!(g1);
+    assert_eq!(g1, g2);
+    assert_eq!(g2.check_invariants(), Ok(()));
+    assert_eq!(g2.neighbors_directed(2, Incoming).count(), 2);
+
+    let g1 = UnGraphMap::<_, ()>::from_edges(&[(2, 1), (2, 2), (3, 2)]);
+    let g2: UnGraphMap<i32, ()> = rejson!(g1);
+    assert_eq!(g1, g2);
+    assert_eq!(g2.check_invariants(), Ok(()));
+    assert_eq!(g2.neighbors(2).count(), 3);
+}
+
+const UNGRAPHMAP_DUPLICATE_EDGE: &str = r#"{
+    "nodes":[1,2,3],
+    "edges":[[1,2,null],[2,3,null],[2,1,null]]
+    }"#;
+
+const GRAPHMAP_MISSING_NODE: &str = r#"{
+    "nodes":[1,2],
+    "edges":[[1,2,null],[2,3,null]]
+    }"#;
+
+#[test]
+fn from_json_digraphmap_reversed_edge() {
+    let g: DiGraphMap<i32, ()> = fromjson!(UNGRAPHMAP_DUPLICATE_EDGE);
+    assert_eq!(g.edge_count(), 3);
+}
+
+#[test]
+#[should_panic(expected = "duplicate edge")]
+fn from_json_ungraphmap_duplicate_edge() {
+    let _: UnGraphMap<i32, ()> = fromjson!(UNGRAPHMAP_DUPLICATE_EDGE);
+}
+
+#[test]
+#[should_panic(expected = "does not exist")]
+fn from_json_graphmap_missing_node() {
+    let _: DiGraphMap<i32, ()> = fromjson!(GRAPHMAP_MISSING_NODE);
+}
+
+#[test]
+fn json_graphmap_str_keys() {
+    let mut g1 = DiGraphMap::new();
+    g1.add_edge("alpha", "beta", 1.5);
+    g1.add_edge("beta", "alpha", 2.5);
+    g1.add_edge("beta", "gamma", 0.);
+    g1.add_node("delta");
+    let json = tojson!(g1);
+    let g2: DiGraphMap<&str, f64> = fromjson!(json);
+    assert_eq!(g1, g2);
+    assert_eq!(g2.check_invariants(), Ok(()));
+    assert_equal(g1.nodes(), g2.nodes());
+    assert_equal(g1.all_edges(), g2.all_edges());
+}
+
+#[test]
+#[should_panic(expected = "duplicate edge")]
+fn from_json_digraphmap_str_duplicate_edge() {
+    let _: DiGraphMap<&str, ()> =
+        fromjson!(r#"{"nodes":["a","b"],"edges":[["a","b",null],["b","a",null],["a","b",null]]}"#);
+}
+
+quickcheck! {
+    fn json_graphmap_to_graphmap(g1: DiGraphMap<i8, i32>) -> () {
+        let g2: DiGraphMap<i8, i32> = rejson!(g1);
+        assert_eq!(g1, g2);
+        assert_eq!(g2.check_invariants(), Ok(()));
+    }
+
+    fn bincode_ungraphmap_to_ungraphmap(g1: UnGraphMap<i8, i32>) -> () {
+        let g2: UnGraphMap<i8, i32> = recode!(g1);
+        assert_eq!(g1, g2);
+        assert_eq!(g2.check_invariants(), Ok(()));
+    }
+}
+
 quickcheck! {
     fn json_graph_to_stablegraph_to_graph(g1: Graph<i32, i32>) -> () {
         let sg: StableGraph<i32, i32> = rejson!(g1);
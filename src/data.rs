@@ -8,6 +8,8 @@ use crate::stable_graph::StableGraph;
 use crate::visit::{Data, NodeCount, NodeIndexable, Reversed};
 use crate::EdgeType;
 use crate::Graph;
+#[cfg(feature = "graphmap")]
+use std::hash::BuildHasher;
 
 trait_template! {
     /// Access node and edge weights (associated data).
@@ -184,10 +186,11 @@ where
 }
 
 #[cfg(feature = "graphmap")]
-impl<N, E, Ty> Build for GraphMap<N, E, Ty>
+impl<N, E, Ty, S> Build for GraphMap<N, E, Ty, S>
 where
     Ty: EdgeType,
     N: NodeTrait,
+    S: BuildHasher,
 {
     fn add_node(&mut self, weight: Self::NodeWeight) -> Self::NodeId {
         self.add_node(weight)
@@ -239,10 +242,11 @@ where
 }
 
 #[cfg(feature = "graphmap")]
-impl<N, E, Ty> Create for GraphMap<N, E, Ty>
+impl<N, E, Ty, S> Create for GraphMap<N, E, Ty, S>
 where
     Ty: EdgeType,
     N: NodeTrait,
+    S: BuildHasher + Default,
 {
     fn with_capacity(nodes: usize, edges: usize) -> Self {
         Self::with_capacity(nodes, edges)
@@ -350,10 +354,11 @@ where
 }
 
 #[cfg(feature = "graphmap")]
-impl<N, E, Ty> FromElements for GraphMap<N, E, Ty>
+impl<N, E, Ty, S> FromElements for GraphMap<N, E, Ty, S>
 where
     Ty: EdgeType,
     N: NodeTrait,
+    S: BuildHasher + Default,
 {
     fn from_elements<I>(iterable: I) -> Self
     where
@@ -230,7 +230,7 @@ where
 }
 
 /// Pass Display formatting through a simple escaping filter
-struct Escaped<T>(T);
+pub(crate) struct Escaped<T>(pub(crate) T);
 
 impl<T> fmt::Display for Escaped<T>
 where
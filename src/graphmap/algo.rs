@@ -0,0 +1,867 @@
+//! Graph algorithms specialized for `GraphMap`, returning results keyed by
+//! node identifiers.
+
+use indexmap::map::Entry::{Occupied, Vacant};
+use indexmap::IndexMap;
+use std::cmp::min;
+use std::collections::{BinaryHeap, HashSet, VecDeque};
+use std::hash::BuildHasher;
+use std::mem;
+
+use crate::algo::{self, Measure};
+use crate::scored::MinScored;
+use crate::unionfind::UnionFind;
+use crate::{Directed, EdgeType, Incoming, Undirected};
+
+use super::{GraphMap, NodeTrait};
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+impl<N, E, Ty, S> GraphMap<N, E, Ty, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    S: BuildHasher,
+{
+    /// Compute the *strongly connected components* using Tarjan's algorithm.
+    ///
+    /// Return a vector where each element is a strongly connected component,
+    /// following outgoing edges only. The order of nodes within each
+    /// component is arbitrary, but the order of the components is their
+    /// postorder (reverse topological sort).
+    ///
+    /// For an undirected graph, the components are simply the connected
+    /// components.
+    ///
+    /// See also [`algo::tarjan_scc`](../algo/fn.tarjan_scc.html).
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<_, ()>::from_edges(&[(1, 2), (2, 1), (2, 3)]);
+    /// let mut sccs = g.tarjan_scc();
+    /// sccs[1].sort();
+    /// assert_eq!(sccs, vec![vec![3], vec![1, 2]]);
+    /// ```
+    pub fn tarjan_scc(&self) -> Vec<Vec<N>> {
+        algo::tarjan_scc(self)
+    }
+
+    /// Return the number of connected components of the graph.
+    ///
+    /// Edge directions are ignored, so for a directed graph this counts the
+    /// weakly connected components. Every isolated node is a component of its
+    /// own.
+    ///
+    /// See also [`algo::connected_components`](../algo/fn.connected_components.html).
+    pub fn connected_components(&self) -> usize {
+        algo::connected_components(self)
+    }
+
+    /// Label every node with the index of its connected component.
+    ///
+    /// Edge directions are ignored. Components are numbered from `0` in the
+    /// iteration order of their first node in `.nodes()`, and the labels are
+    /// returned in the same order.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let mut g = UnGraphMap::<_, ()>::from_edges(&[('a', 'b'), ('c', 'd'), ('d', 'b')]);
+    /// g.add_node('e');
+    /// let labels = g.component_labels();
+    /// assert_eq!(labels[&'c'], 0);
+    /// assert_eq!(labels[&'e'], 1);
+    /// ```
+    pub fn component_labels(&self) -> IndexMap<N, usize, S>
+    where
+        S: Clone,
+    {
+        let mut vertex_sets = UnionFind::new(self.node_count());
+        for &(a, b) in self.edges.keys() {
+            let a = self.nodes.get_index_of(&a).unwrap();
+            let b = self.nodes.get_index_of(&b).unwrap();
+            vertex_sets.union(a, b);
+        }
+        let representatives = vertex_sets.into_labeling();
+        let mut component = vec![None; representatives.len()];
+        let mut count = 0;
+        let mut labels =
+            IndexMap::with_capacity_and_hasher(self.node_count(), self.nodes.hasher().clone());
+        for (&n, rep) in self.nodes.keys().zip(representatives) {
+            let label = *component[rep].get_or_insert_with(|| {
+                count += 1;
+                count - 1
+            });
+            labels.insert(n, label);
+        }
+        labels
+    }
+
+    /// Return `true` if there is a path from `a` to `b`, following outgoing
+    /// edges in a directed graph and any edge in an undirected graph.
+    ///
+    /// A node is always connected to itself. Return `false` if either node
+    /// doesn't exist. The search stops as soon as `b` is reached.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<_, ()>::from_edges(&[(1, 2), (2, 3)]);
+    /// assert!(g.has_path_connecting(1, 3));
+    /// assert!(!g.has_path_connecting(3, 1));
+    /// ```
+    pub fn has_path_connecting(&self, a: N, b: N) -> bool {
+        self.has_path_connecting_with_visited(a, b, &mut HashSet::new())
+    }
+
+    /// Return `true` if there is a path from `a` to `b`, like
+    /// `has_path_connecting`, reusing `visited` as the set of visited nodes.
+    ///
+    /// `visited` is cleared before the search, and holds the visited nodes
+    /// afterwards.
+    pub fn has_path_connecting_with_visited(&self, a: N, b: N, visited: &mut HashSet<N>) -> bool {
+        visited.clear();
+        if !self.contains_node(a) || !self.contains_node(b) {
+            return false;
+        }
+        let mut stack = vec![a];
+        visited.insert(a);
+        while let Some(n) = stack.pop() {
+            if n == b {
+                return true;
+            }
+            for succ in self.neighbors(n) {
+                if visited.insert(succ) {
+                    stack.push(succ);
+                }
+            }
+        }
+        false
+    }
+
+    /// Dijkstra's shortest path algorithm.
+    ///
+    /// Compute the length of the shortest path from `start` to every reachable
+    /// node, following outgoing edges in a directed graph and any edge in an
+    /// undirected graph. `edge_cost` is called with the endpoints and weight
+    /// of an edge and returns its cost. Edge costs must be non-negative.
+    ///
+    /// Return a map from each reachable node, including `start`, to the
+    /// length of its shortest path from `start`. Nodes are in the order they
+    /// were first reached. The map is empty if `start` doesn't exist.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<_, u32>::from_edges(&[("a", "b", 5), ("a", "c", 1), ("c", "b", 2)]);
+    /// let costs = g.dijkstra("a", |_, _, &w| w);
+    /// assert_eq!(costs["b"], 3);
+    /// ```
+    pub fn dijkstra<F, K>(&self, start: N, mut edge_cost: F) -> IndexMap<N, K, S>
+    where
+        F: FnMut(N, N, &E) -> K,
+        K: Measure + Copy,
+        S: Clone,
+    {
+        let mut visited = HashSet::with_capacity(self.node_count());
+        let mut scores = IndexMap::with_hasher(self.nodes.hasher().clone());
+        if !self.contains_node(start) {
+            return scores;
+        }
+        let mut visit_next = BinaryHeap::new();
+        let zero_score = K::default();
+        scores.insert(start, zero_score);
+        visit_next.push(MinScored(zero_score, start));
+        while let Some(MinScored(node_score, node)) = visit_next.pop() {
+            if !visited.insert(node) {
+                continue;
+            }
+            for (a, next, weight) in self.edges(node) {
+                if visited.contains(&next) {
+                    continue;
+                }
+                let next_score = node_score + edge_cost(a, next, weight);
+                match scores.entry(next) {
+                    Occupied(ent) => {
+                        if next_score < *ent.get() {
+                            *ent.into_mut() = next_score;
+                            visit_next.push(MinScored(next_score, next));
+                        }
+                    }
+                    Vacant(ent) => {
+                        ent.insert(next_score);
+                        visit_next.push(MinScored(next_score, next));
+                    }
+                }
+            }
+        }
+        scores
+    }
+
+    /// Return `true` if the graph contains a cycle.
+    ///
+    /// A directed graph is searched for a back edge depth-first; an
+    /// undirected graph has a cycle if any edge joins two nodes that are
+    /// already connected. Self loops are cycles.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let mut g = DiGraphMap::<_, ()>::from_edges(&[(1, 2), (2, 3), (1, 3)]);
+    /// assert!(!g.is_cyclic());
+    /// g.add_edge(3, 1, ());
+    /// assert!(g.is_cyclic());
+    /// ```
+    pub fn is_cyclic(&self) -> bool {
+        if Ty::is_directed() {
+            algo::is_cyclic_directed(self)
+        } else {
+            algo::is_cyclic_undirected(self)
+        }
+    }
+
+    /// Return the *eccentricity* of every node: the largest number of edges
+    /// on a shortest path from it to a node it can reach.
+    ///
+    /// Edge weights are ignored. For a directed graph, paths follow the
+    /// direction of the edges. Nodes that can't reach any other node have an
+    /// eccentricity of `0`.
+    ///
+    /// Computes in **O(|V| (|V| + |E|))** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2)]);
+    /// let ecc = g.eccentricities();
+    /// assert_eq!(ecc[&0], 2);
+    /// assert_eq!(ecc[&1], 1);
+    /// ```
+    pub fn eccentricities(&self) -> IndexMap<N, usize, S>
+    where
+        S: Clone,
+    {
+        let mut ecc =
+            IndexMap::with_capacity_and_hasher(self.node_count(), self.nodes.hasher().clone());
+        let mut discovered = HashSet::new();
+        for &n in self.nodes.keys() {
+            ecc.insert(n, self.bfs_depth(n, &mut discovered));
+        }
+        ecc
+    }
+
+    /// Return the *diameter* of the graph: the largest eccentricity of its
+    /// nodes, see `eccentricities`.
+    ///
+    /// Return `None` if the graph has no nodes, or if some node can't reach
+    /// every other node, so that the diameter is infinite.
+    ///
+    /// Computes in **O(|V| (|V| + |E|))** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let mut g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    /// assert_eq!(g.diameter(), Some(2));
+    /// g.remove_edge(2, 0);
+    /// assert_eq!(g.diameter(), None);
+    /// ```
+    pub fn diameter(&self) -> Option<usize> {
+        let mut discovered = HashSet::new();
+        let mut diameter = None;
+        for &n in self.nodes.keys() {
+            let depth = self.bfs_depth(n, &mut discovered);
+            if discovered.len() != self.node_count() {
+                return None;
+            }
+            diameter = diameter.max(Some(depth));
+        }
+        diameter
+    }
+
+    /// Return the nodes within `k` hops of `source`, mapped to their hop
+    /// distance from it, in breadth-first order.
+    ///
+    /// `source` is at distance `0`. For a directed graph, paths follow the
+    /// direction of the edges. Return an empty map if `source` doesn't
+    /// exist.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (4, 0)]);
+    /// let hops = g.k_hop_neighbors(0, 2);
+    /// assert_eq!(hops.into_iter().collect::<Vec<_>>(), vec![(0, 0), (1, 1), (2, 2)]);
+    /// ```
+    pub fn k_hop_neighbors(&self, source: N, k: usize) -> IndexMap<N, usize, S>
+    where
+        S: Clone,
+    {
+        let mut hops = IndexMap::with_hasher(self.nodes.hasher().clone());
+        if !self.contains_node(source) {
+            return hops;
+        }
+        hops.insert(source, 0);
+        let mut queue = VecDeque::new();
+        queue.push_back((source, 0));
+        while let Some((n, d)) = queue.pop_front() {
+            if d == k {
+                continue;
+            }
+            for m in self.neighbors(n) {
+                if let Vacant(entry) = hops.entry(m) {
+                    entry.insert(d + 1);
+                    queue.push_back((m, d + 1));
+                }
+            }
+        }
+        hops
+    }
+
+    /// Return the depth of a breadth-first search from `start`, leaving the
+    /// reached nodes in `discovered`.
+    fn bfs_depth(&self, start: N, discovered: &mut HashSet<N>) -> usize {
+        discovered.clear();
+        discovered.insert(start);
+        let mut queue = VecDeque::new();
+        queue.push_back((start, 0));
+        let mut depth = 0;
+        while let Some((n, d)) = queue.pop_front() {
+            depth = d;
+            for m in self.neighbors(n) {
+                if discovered.insert(m) {
+                    queue.push_back((m, d + 1));
+                }
+            }
+        }
+        depth
+    }
+
+    /// Return `true` if the graph is isomorphic to `other`: if there is a
+    /// bijection between their nodes that maps the edges of one graph exactly
+    /// onto the edges of the other. Node identifiers and edge weights are
+    /// ignored.
+    ///
+    /// Graphs with a different number of nodes or edges, or different degree
+    /// sequences, are rejected before the VF2 search of `algo::is_isomorphic`,
+    /// which is exponential in the worst case.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let triangle = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    /// let other = UnGraphMap::<_, ()>::from_edges(&[(7, 5), (5, 9), (9, 7)]);
+    /// let path = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2)]);
+    /// assert!(triangle.is_isomorphic_to(&other));
+    /// assert!(!triangle.is_isomorphic_to(&path));
+    /// ```
+    pub fn is_isomorphic_to(&self, other: &Self) -> bool {
+        self.node_count() == other.node_count()
+            && self.edge_count() == other.edge_count()
+            && self.degree_sequence() == other.degree_sequence()
+            && algo::is_isomorphic(self, other)
+    }
+
+    /// Return the *k-core* of the graph: the largest subgraph in which every
+    /// node has a degree of at least `k`, with edge weights cloned.
+    ///
+    /// Nodes with a degree less than `k` are removed repeatedly, until none
+    /// are left. Degrees are counted like `degree`. The result is empty if no
+    /// such subgraph exists.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3)]);
+    /// let core = g.k_core(2);
+    /// assert_eq!(core.node_count(), 3);
+    /// assert!(!core.contains_node(3));
+    /// ```
+    pub fn k_core(&self, k: usize) -> Self
+    where
+        E: Clone,
+        S: Clone,
+    {
+        let mut degree = self
+            .nodes
+            .keys()
+            .map(|&n| self.degree(n))
+            .collect::<Vec<_>>();
+        let mut removed = vec![false; degree.len()];
+        let mut stack = (0..degree.len())
+            .filter(|&i| degree[i] < k)
+            .collect::<Vec<_>>();
+        for &i in &stack {
+            removed[i] = true;
+        }
+        while let Some(i) = stack.pop() {
+            let neighbors = &self.nodes[i];
+            for &(m, _) in neighbors {
+                let j = self.nodes.get_index_of(&m).unwrap();
+                if removed[j] {
+                    continue;
+                }
+                degree[j] -= 1;
+                if degree[j] < k {
+                    removed[j] = true;
+                    stack.push(j);
+                }
+            }
+        }
+        self.subgraph(
+            self.nodes
+                .keys()
+                .zip(removed)
+                .filter(|&(_, removed)| !removed)
+                .map(|(&n, _)| n),
+        )
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<N, E, Ty, S> GraphMap<N, E, Ty, S>
+where
+    N: NodeTrait + Send + Sync,
+    E: Sync,
+    Ty: EdgeType + Sync,
+    S: BuildHasher + Sync,
+{
+    /// Compute the length of the shortest paths between all pairs of nodes,
+    /// running `dijkstra` from every node in parallel.
+    ///
+    /// Return a map from each node, in the iteration order of `.nodes()`, to
+    /// the result of `dijkstra` from that node.
+    ///
+    /// Requires crate feature `"rayon"`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let g = UnGraphMap::<_, u32>::from_edges(&[(0, 1, 2), (1, 2, 3)]);
+    /// let costs = g.par_all_pairs_dijkstra(|_, _, &w| w);
+    /// assert_eq!(costs[&0][&2], 5);
+    /// assert_eq!(costs[&2][&0], 5);
+    /// ```
+    pub fn par_all_pairs_dijkstra<F, K>(&self, edge_cost: F) -> IndexMap<N, IndexMap<N, K, S>, S>
+    where
+        F: Fn(N, N, &E) -> K + Sync,
+        K: Measure + Copy + Send,
+        S: Clone + Send,
+    {
+        let nodes = self.nodes.keys().cloned().collect::<Vec<_>>();
+        let costs = nodes
+            .par_iter()
+            .map(|&n| self.dijkstra(n, &edge_cost))
+            .collect::<Vec<_>>();
+        let mut all_pairs =
+            IndexMap::with_capacity_and_hasher(nodes.len(), self.nodes.hasher().clone());
+        all_pairs.extend(nodes.into_iter().zip(costs));
+        all_pairs
+    }
+}
+
+impl<N, E, S> GraphMap<N, E, Directed, S>
+where
+    N: NodeTrait,
+    S: BuildHasher,
+{
+    /// Perform a topological sort of the graph with Kahn's algorithm.
+    ///
+    /// If the graph is acyclic, return all its nodes, including isolated
+    /// nodes, in an order where every node comes before its successors.
+    /// Otherwise, return `Err` with a node on a cycle. Self loops are also
+    /// cycles.
+    ///
+    /// Ties are broken by the iteration order of `.nodes()`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let mut g = DiGraphMap::<_, ()>::from_edges(&[("std", "core"), ("alloc", "core"), ("std", "alloc")]);
+    /// assert_eq!(g.toposort(), Ok(vec!["std", "alloc", "core"]));
+    /// g.add_edge("core", "std", ());
+    /// assert!(g.toposort().is_err());
+    /// ```
+    pub fn toposort(&self) -> Result<Vec<N>, N> {
+        let index = |n: &N| self.nodes.get_index_of(n).unwrap();
+        let mut in_degree = self
+            .nodes
+            .keys()
+            .map(|&n| self.neighbors_directed(n, Incoming).count())
+            .collect::<Vec<_>>();
+        let mut ready = (0..in_degree.len())
+            .filter(|&i| in_degree[i] == 0)
+            .collect::<VecDeque<_>>();
+        let mut order = Vec::with_capacity(self.node_count());
+        while let Some(i) = ready.pop_front() {
+            let (&n, _) = self.nodes.get_index(i).unwrap();
+            order.push(n);
+            for succ in self.neighbors(n) {
+                let j = index(&succ);
+                in_degree[j] -= 1;
+                if in_degree[j] == 0 {
+                    ready.push_back(j);
+                }
+            }
+        }
+        if order.len() == self.node_count() {
+            return Ok(order);
+        }
+        // Every remaining node has a remaining predecessor; walking
+        // backwards through them |V| times must end up on a cycle.
+        let mut i = (0..in_degree.len()).find(|&i| in_degree[i] > 0).unwrap();
+        for _ in 0..self.node_count() {
+            let (&n, _) = self.nodes.get_index(i).unwrap();
+            i = self
+                .neighbors_directed(n, Incoming)
+                .map(|pred| index(&pred))
+                .find(|&j| in_degree[j] > 0)
+                .unwrap();
+        }
+        Err(*self.nodes.get_index(i).unwrap().0)
+    }
+
+    /// Return the number of pairs of distinct nodes connected by edges in
+    /// both directions, counting each pair once.
+    ///
+    /// Self loops are not counted.
+    ///
+    /// Computes in **O(|E|)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 0), (1, 2), (2, 2)]);
+    /// assert_eq!(g.reciprocal_edges(), 1);
+    /// ```
+    pub fn reciprocal_edges(&self) -> usize {
+        self.edges
+            .keys()
+            .filter(|&&(a, b)| a < b && self.edges.contains_key(&(b, a)))
+            .count()
+    }
+
+    /// Return the *transitive closure* of the graph: a graph with the same
+    /// nodes and an edge `a -> b` whenever `b` is reachable from `a`.
+    ///
+    /// A node only gets a self loop if it is on a cycle, including a self
+    /// loop of the original graph.
+    ///
+    /// Computes in **O(|V| (|V| + |E|))** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<_, ()>::from_edges(&[('a', 'b'), ('b', 'c')]);
+    /// let closure = g.transitive_closure();
+    /// assert_eq!(closure.edge_count(), 3);
+    /// assert!(closure.contains_edge('a', 'c'));
+    /// ```
+    pub fn transitive_closure(&self) -> GraphMap<N, (), Directed, S>
+    where
+        S: Clone,
+    {
+        let mut closure = GraphMap::with_hasher(self.nodes.hasher().clone());
+        for &n in self.nodes.keys() {
+            closure.add_node(n);
+        }
+        let mut discovered = HashSet::new();
+        let mut queue = VecDeque::new();
+        for &start in self.nodes.keys() {
+            discovered.clear();
+            queue.push_back(start);
+            while let Some(n) = queue.pop_front() {
+                for succ in self.neighbors(n) {
+                    if discovered.insert(succ) {
+                        closure.add_edge(start, succ, ());
+                        queue.push_back(succ);
+                    }
+                }
+            }
+        }
+        closure
+    }
+}
+
+impl<N, S> GraphMap<N, f64, Directed, S>
+where
+    N: NodeTrait,
+    S: BuildHasher,
+{
+    /// Compute the *PageRank* of every node with `iterations` rounds of power
+    /// iteration, using the edge weights as transition weights.
+    ///
+    /// From each node, the random surfer follows an outgoing edge with
+    /// probability `damping`, choosing it in proportion to its weight, and
+    /// jumps to a uniformly random node otherwise. The rank of nodes without
+    /// outgoing edges (or whose outgoing weights sum to zero) is spread
+    /// uniformly over all nodes. The scores sum to `1.0`, up to rounding.
+    ///
+    /// Computes in **O(iterations (|V| + |E|))** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::from_edges(&[("a", "b", 1.), ("b", "a", 1.)]);
+    /// let rank = g.pagerank(0.85, 20);
+    /// assert!((rank["a"] - 0.5).abs() < 1e-9);
+    /// assert!((rank["b"] - 0.5).abs() < 1e-9);
+    /// ```
+    pub fn pagerank(&self, damping: f64, iterations: usize) -> IndexMap<N, f64, S>
+    where
+        S: Clone,
+    {
+        let node_count = self.node_count();
+        let mut out_weight = vec![0.; node_count];
+        let transitions: Vec<_> = self
+            .edges
+            .iter()
+            .map(|(&(a, b), &weight)| {
+                let a = self.nodes.get_index_of(&a).unwrap();
+                let b = self.nodes.get_index_of(&b).unwrap();
+                out_weight[a] += weight;
+                (a, b, weight)
+            })
+            .collect();
+
+        let n = node_count as f64;
+        let mut rank = vec![1. / n; node_count];
+        let mut next_rank = vec![0.; node_count];
+        for _ in 0..iterations {
+            let dangling: f64 = rank
+                .iter()
+                .zip(&out_weight)
+                .filter(|&(_, &w)| w <= 0.)
+                .map(|(&r, _)| r)
+                .sum();
+            let base = (1. - damping) / n + damping * dangling / n;
+            next_rank.clear();
+            next_rank.resize(node_count, base);
+            for &(a, b, weight) in &transitions {
+                if out_weight[a] > 0. {
+                    next_rank[b] += damping * rank[a] * weight / out_weight[a];
+                }
+            }
+            mem::swap(&mut rank, &mut next_rank);
+        }
+
+        let mut scores =
+            IndexMap::with_capacity_and_hasher(node_count, self.nodes.hasher().clone());
+        for (&node, r) in self.nodes.keys().zip(rank) {
+            scores.insert(node, r);
+        }
+        scores
+    }
+}
+
+impl<N, E, S> GraphMap<N, E, Undirected, S>
+where
+    N: NodeTrait,
+    S: BuildHasher,
+{
+    /// Color the nodes with two colors, `false` and `true`, so that no edge
+    /// connects two nodes of the same color, if the graph is bipartite.
+    ///
+    /// Each connected component is colored with a breadth-first search; the
+    /// first node of a component in the iteration order of `.nodes()` gets
+    /// `false`. Return `None` if the graph has an odd cycle, which includes
+    /// self loops.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let mut g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 0)]);
+    /// let colors = g.two_coloring().unwrap();
+    /// assert_eq!(colors[&0], colors[&2]);
+    /// assert_ne!(colors[&0], colors[&1]);
+    /// g.add_edge(0, 2, ());
+    /// assert_eq!(g.two_coloring(), None);
+    /// ```
+    pub fn two_coloring(&self) -> Option<IndexMap<N, bool, S>>
+    where
+        S: Clone,
+    {
+        let mut colors =
+            IndexMap::with_capacity_and_hasher(self.node_count(), self.nodes.hasher().clone());
+        let mut queue = VecDeque::new();
+        for &start in self.nodes.keys() {
+            if colors.contains_key(&start) {
+                continue;
+            }
+            colors.insert(start, false);
+            queue.push_back(start);
+            while let Some(n) = queue.pop_front() {
+                let color = colors[&n];
+                for m in self.neighbors(n) {
+                    match colors.entry(m) {
+                        Occupied(ent) => {
+                            if *ent.get() == color {
+                                return None;
+                            }
+                        }
+                        Vacant(ent) => {
+                            ent.insert(!color);
+                            queue.push_back(m);
+                        }
+                    }
+                }
+            }
+        }
+        Some(colors)
+    }
+
+    /// Return the *articulation points* of the graph: the nodes whose removal
+    /// increases the number of connected components.
+    ///
+    /// The nodes are in the iteration order of `.nodes()`.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3)]);
+    /// assert_eq!(g.articulation_points(), vec![2]);
+    /// ```
+    pub fn articulation_points(&self) -> Vec<N> {
+        let (is_cut, _) = self.cuts();
+        self.nodes
+            .keys()
+            .zip(is_cut)
+            .filter(|&(_, is_cut)| is_cut)
+            .map(|(&n, _)| n)
+            .collect()
+    }
+
+    /// Return the *bridges* of the graph: the edges whose removal increases
+    /// the number of connected components.
+    ///
+    /// Each bridge is given with the smaller node first. Self loops are never
+    /// bridges.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (3, 2)]);
+    /// assert_eq!(g.bridges(), vec![(2, 3)]);
+    /// ```
+    pub fn bridges(&self) -> Vec<(N, N)> {
+        self.cuts().1
+    }
+
+    /// Find the articulation points, by node index, and the bridges with a
+    /// depth-first search computing discovery times and low points.
+    fn cuts(&self) -> (Vec<bool>, Vec<(N, N)>) {
+        const UNDISCOVERED: usize = usize::MAX;
+        let index = |n: &N| self.nodes.get_index_of(n).unwrap();
+        let mut discovery = vec![UNDISCOVERED; self.node_count()];
+        let mut low = vec![0; self.node_count()];
+        let mut is_cut = vec![false; self.node_count()];
+        let mut bridges = Vec::new();
+        let mut time = 0;
+        let mut stack = Vec::new();
+        for (root, &start) in self.nodes.keys().enumerate() {
+            if discovery[root] != UNDISCOVERED {
+                continue;
+            }
+            discovery[root] = time;
+            low[root] = time;
+            time += 1;
+            let mut root_children = 0;
+            stack.push((start, root, None, self.neighbors(start)));
+            while let Some(&mut (n, i, parent, ref mut neighbors)) = stack.last_mut() {
+                if let Some(m) = neighbors.next() {
+                    let j = index(&m);
+                    if i == j || parent == Some(j) {
+                        continue;
+                    }
+                    if discovery[j] == UNDISCOVERED {
+                        discovery[j] = time;
+                        low[j] = time;
+                        time += 1;
+                        stack.push((m, j, Some(i), self.neighbors(m)));
+                    } else {
+                        low[i] = min(low[i], discovery[j]);
+                    }
+                    continue;
+                }
+                stack.pop();
+                if let Some(p) = parent {
+                    low[p] = min(low[p], low[i]);
+                    if low[i] > discovery[p] {
+                        bridges.push(Self::edge_key(*self.nodes.get_index(p).unwrap().0, n));
+                    }
+                    if p == root {
+                        root_children += 1;
+                    } else if low[i] >= discovery[p] {
+                        is_cut[p] = true;
+                    }
+                }
+            }
+            is_cut[root] = root_children > 1;
+        }
+        (is_cut, bridges)
+    }
+
+    /// Compute a *minimum spanning tree* with Kruskal's algorithm, with edge
+    /// weights cloned.
+    ///
+    /// The result has all the nodes of the graph; if the graph is not
+    /// connected, it is a spanning forest with one tree per component. Self
+    /// loops are never part of it.
+    ///
+    /// Computes in **O(|E| log |E|)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let g = UnGraphMap::<_, _>::from_edges(&[("a", "b", 1), ("b", "c", 2), ("a", "c", 3)]);
+    /// let mst = g.minimum_spanning_tree();
+    /// assert_eq!(mst.node_count(), 3);
+    /// assert!(mst.contains_edge("a", "b"));
+    /// assert!(mst.contains_edge("b", "c"));
+    /// assert!(!mst.contains_edge("a", "c"));
+    /// ```
+    pub fn minimum_spanning_tree(&self) -> Self
+    where
+        E: Clone + PartialOrd,
+        S: Clone,
+    {
+        let node_count = self.node_count();
+        let mut mst = GraphMap::with_capacity_and_hasher(
+            node_count,
+            node_count.saturating_sub(1),
+            self.nodes.hasher().clone(),
+        );
+        for &n in self.nodes.keys() {
+            mst.add_node(n);
+        }
+        let mut sort_edges = self
+            .edges
+            .values()
+            .enumerate()
+            .map(|(i, w)| MinScored(w, i))
+            .collect::<BinaryHeap<_>>();
+        let mut subgraphs = UnionFind::new(node_count);
+        while let Some(MinScored(weight, i)) = sort_edges.pop() {
+            let (&(a, b), _) = self.edges.get_index(i).unwrap();
+            let ia = self.nodes.get_index_of(&a).unwrap();
+            let ib = self.nodes.get_index_of(&b).unwrap();
+            if subgraphs.union(ia, ib) {
+                mst.add_edge(a, b, weight.clone());
+                if mst.edge_count() + 1 == node_count {
+                    break;
+                }
+            }
+        }
+        mst
+    }
+}
@@ -0,0 +1,3911 @@
+//! `GraphMap<N, E, Ty>` is a graph datastructure where node values are mapping
+//! keys.
+
+use indexmap::map::Entry::{Occupied, Vacant};
+use indexmap::map::Keys;
+use indexmap::map::{
+    IntoIter as IndexMapIntoIter, Iter as IndexMapIter, IterMut as IndexMapIterMut,
+};
+use indexmap::IndexMap;
+use std::borrow::Borrow;
+use std::cmp::{self, Ordering};
+use std::collections::hash_map::{DefaultHasher, RandomState};
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{self, BuildHasher, Hash, Hasher};
+use std::iter::FromIterator;
+use std::iter::{Cloned, DoubleEndedIterator};
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::{Add, Deref, Index, IndexMut};
+use std::slice::Iter;
+use std::vec;
+
+use crate::{Directed, Direction, EdgeType, Incoming, Outgoing, Undirected};
+
+use crate::dot::Escaped;
+use crate::graph::Graph;
+use crate::graph::{IndexType, NodeIndex};
+use crate::visit::{IntoEdgeReferences, IntoEdges, IntoEdgesDirected, NodeCompactIndexable};
+use crate::visit::{IntoNodeIdentifiers, IntoNodeReferences, NodeCount, NodeIndexable};
+use crate::IntoWeightedEdge;
+#[cfg(feature = "rand")]
+use rand::Rng;
+
+mod algo;
+mod multi;
+#[cfg(feature = "serde-1")]
+mod serialization;
+mod view;
+
+pub use self::multi::{
+    DiMultiGraphMap, EdgeHandle, EdgesConnecting, MultiAllEdges, MultiEdges, MultiGraphMap,
+    UnMultiGraphMap,
+};
+pub use self::view::{
+    GraphView, ViewEdgeReferences, ViewEdges, ViewNeighbors, ViewNodeIdentifiers,
+};
+
+/// A `GraphMap` with undirected edges.
+///
+/// For example, an edge between *1* and *2* is equivalent to an edge between
+/// *2* and *1*.
+pub type UnGraphMap<N, E> = GraphMap<N, E, Undirected>;
+/// A `GraphMap` with directed edges.
+///
+/// For example, an edge from *1* to *2* is distinct from an edge from *2* to
+/// *1*.
+pub type DiGraphMap<N, E> = GraphMap<N, E, Directed>;
+/// A map from the nodes of a `GraphMap` to their node indices in a `Graph`,
+/// as returned by `GraphMap::to_graph`.
+pub type NodeIndexMap<N, Ix, S = RandomState> = IndexMap<N, NodeIndex<Ix>, S>;
+
+/// `GraphMap::from_sorted_edges` error: edges were not in sorted order, or
+/// an edge was repeated.
+#[derive(Clone, Debug)]
+pub struct UnsortedError<N> {
+    first_error: (N, N),
+}
+
+impl<N: Copy> UnsortedError<N> {
+    /// Return the endpoints of the first edge that was out of order.
+    pub fn first_error(&self) -> (N, N) {
+        self.first_error
+    }
+}
+
+/// `GraphMap<N, E, Ty>` is a graph datastructure using an associative array
+/// of its node weights `N`.
+///
+/// It uses an combined adjacency list and sparse adjacency matrix
+/// representation, using **O(|V| + |E|)** space, and allows testing for edge
+/// existence in constant time.
+///
+/// `GraphMap` is parameterized over:
+///
+/// - Associated data `N` for nodes and `E` for edges, called *weights*.
+/// - The node weight `N` must implement `Copy` and will be used as node
+/// identifier, duplicated into several places in the data structure.
+/// It must be suitable as a hash table key (implementing `Eq + Hash`).
+/// The node type must also implement `Ord` so that the implementation can
+/// order the pair (`a`, `b`) for an edge connecting any two nodes `a` and `b`.
+/// - `E` can be of arbitrary type.
+/// - Edge type `Ty` that determines whether the graph edges are directed or
+/// undirected.
+/// - Hash builder `S` that is used to hash the node identifiers, `RandomState`
+///   by default.
+///
+/// You can use the type aliases `UnGraphMap` and `DiGraphMap` for convenience.
+///
+/// `GraphMap` does not allow parallel edges, but self loops are allowed. Use
+/// [`MultiGraphMap`](struct.MultiGraphMap.html) if you need parallel edges.
+///
+/// Nodes and edges are iterated in a deterministic order, which only depends
+/// on the sequence of operations on the graph: new nodes and edges are
+/// appended at the end. Removing a node or an edge moves the last one into
+/// its place, and removing a node removes its edges in this way too. Use
+/// `sort_nodes` and `sort_edges` to restore a canonical order.
+///
+/// The `Copy` bound on `N` is required by the graph traits in
+/// [`visit`](../visit/index.html), which hand out node identifiers by value.
+/// To use node identifiers that are not `Copy`, such as `String`, keep the
+/// owned values elsewhere and key the graph by references to them, like
+/// `&str`, or by [`Ptr`](struct.Ptr.html) if they should be compared by
+/// address:
+///
+/// ```
+/// use petgraph::graphmap::UnGraphMap;
+///
+/// let names = vec!["alice".to_string(), "bob".to_string()];
+/// let mut g = UnGraphMap::<&str, ()>::new();
+/// g.add_edge(&names[0], &names[1], ());
+/// assert!(g.contains_edge("bob", "alice"));
+/// ```
+///
+/// The node identifiers are the only node weights. Mutable node data can be
+/// kept in a map keyed by the identifiers, or the identifier can be a
+/// reference to it, with interior mutability where needed:
+///
+/// ```
+/// use petgraph::graphmap::{DiGraphMap, Ptr};
+/// use std::cell::Cell;
+///
+/// let visits = vec![Cell::new(0), Cell::new(0)];
+/// let mut g = DiGraphMap::<_, ()>::new();
+/// g.add_edge(Ptr(&visits[0]), Ptr(&visits[1]), ());
+/// for n in g.neighbors(Ptr(&visits[0])) {
+///     n.set(n.get() + 1);
+/// }
+/// assert_eq!(visits[1].get(), 1);
+/// ```
+///
+/// Depends on crate feature `graphmap` (default).
+pub struct GraphMap<N, E, Ty, S = RandomState> {
+    nodes: IndexMap<N, Vec<(N, CompactDirection)>, S>,
+    edges: IndexMap<(N, N), E, S>,
+    ty: PhantomData<Ty>,
+}
+
+impl<N, E, Ty, S> Clone for GraphMap<N, E, Ty, S>
+where
+    N: Clone,
+    E: Clone,
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        GraphMap {
+            nodes: self.nodes.clone(),
+            edges: self.edges.clone(),
+            ty: self.ty,
+        }
+    }
+
+    fn clone_from(&mut self, rhs: &Self) {
+        self.nodes.clone_from(&rhs.nodes);
+        self.edges.clone_from(&rhs.edges);
+        self.ty = rhs.ty;
+    }
+}
+
+impl<N: Eq + Hash + fmt::Debug, E: fmt::Debug, Ty: EdgeType, S: BuildHasher> fmt::Debug
+    for GraphMap<N, E, Ty, S>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.nodes.fmt(f)
+    }
+}
+
+/// A trait group for `GraphMap`'s node identifier.
+pub trait NodeTrait: Copy + Ord + Hash {}
+impl<N> NodeTrait for N where N: Copy + Ord + Hash {}
+
+// non-repr(usize) version of Direction
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum CompactDirection {
+    Outgoing,
+    Incoming,
+}
+
+impl CompactDirection {
+    /// Return the corresponding `Direction`.
+    #[inline]
+    fn direction(self) -> Direction {
+        match self {
+            CompactDirection::Outgoing => Outgoing,
+            CompactDirection::Incoming => Incoming,
+        }
+    }
+
+    /// Return the opposite direction.
+    #[inline]
+    fn opposite(self) -> Direction {
+        match self {
+            CompactDirection::Outgoing => Incoming,
+            CompactDirection::Incoming => Outgoing,
+        }
+    }
+}
+
+impl From<Direction> for CompactDirection {
+    fn from(d: Direction) -> Self {
+        match d {
+            Outgoing => CompactDirection::Outgoing,
+            Incoming => CompactDirection::Incoming,
+        }
+    }
+}
+
+impl PartialEq<Direction> for CompactDirection {
+    fn eq(&self, rhs: &Direction) -> bool {
+        (*self as usize) == (*rhs as usize)
+    }
+}
+
+/// Insert the adjacency list entries of a new edge from `a` to `b`,
+/// inserting the nodes if they don't exist.
+fn link_nodes<N, S>(nodes: &mut IndexMap<N, Vec<(N, CompactDirection)>, S>, a: N, b: N)
+where
+    N: NodeTrait,
+    S: BuildHasher,
+{
+    nodes
+        .entry(a)
+        .or_insert_with(|| Vec::with_capacity(1))
+        .push((b, CompactDirection::Outgoing));
+    if a != b {
+        // self loops don't have the Incoming entry
+        nodes
+            .entry(b)
+            .or_insert_with(|| Vec::with_capacity(1))
+            .push((a, CompactDirection::Incoming));
+    }
+}
+
+impl<N, E, Ty, S> GraphMap<N, E, Ty, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    S: BuildHasher,
+{
+    /// Create a new `GraphMap`
+    pub fn new() -> Self
+    where
+        S: Default,
+    {
+        Self::default()
+    }
+
+    /// Create a new `GraphMap` with estimated capacity.
+    pub fn with_capacity(nodes: usize, edges: usize) -> Self
+    where
+        S: Default,
+    {
+        GraphMap {
+            nodes: IndexMap::with_capacity_and_hasher(nodes, S::default()),
+            edges: IndexMap::with_capacity_and_hasher(edges, S::default()),
+            ty: PhantomData,
+        }
+    }
+
+    /// Create a new `GraphMap` that uses `hasher` to hash the node
+    /// identifiers.
+    ///
+    /// ```
+    /// use petgraph::graphmap::GraphMap;
+    /// use petgraph::Directed;
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let mut g = GraphMap::<_, _, Directed, _>::with_hasher(RandomState::new());
+    /// g.add_edge(1, 2, 0.5);
+    /// assert!(g.contains_edge(1, 2));
+    /// ```
+    pub fn with_hasher(hasher: S) -> Self
+    where
+        S: Clone,
+    {
+        Self::with_capacity_and_hasher(0, 0, hasher)
+    }
+
+    /// Create a new `GraphMap` with estimated capacity, that uses `hasher`
+    /// to hash the node identifiers.
+    pub fn with_capacity_and_hasher(nodes: usize, edges: usize, hasher: S) -> Self
+    where
+        S: Clone,
+    {
+        GraphMap {
+            nodes: IndexMap::with_capacity_and_hasher(nodes, hasher.clone()),
+            edges: IndexMap::with_capacity_and_hasher(edges, hasher),
+            ty: PhantomData,
+        }
+    }
+
+    /// Return a reference to the graph's hash builder.
+    pub fn hasher(&self) -> &S {
+        self.nodes.hasher()
+    }
+
+    /// Return the current node and edge capacity of the graph.
+    pub fn capacity(&self) -> (usize, usize) {
+        (self.nodes.capacity(), self.edges.capacity())
+    }
+
+    /// Reserve capacity for at least `additional` more nodes to be inserted
+    /// in the graph.
+    ///
+    /// **Panics** if the new capacity overflows `usize`.
+    pub fn reserve_nodes(&mut self, additional: usize) {
+        self.nodes.reserve(additional);
+    }
+
+    /// Reserve capacity for at least `additional` more edges to be inserted
+    /// in the graph.
+    ///
+    /// **Panics** if the new capacity overflows `usize`.
+    pub fn reserve_edges(&mut self, additional: usize) {
+        self.edges.reserve(additional);
+    }
+
+    /// Shrink the capacity of the graph, including each node's adjacency
+    /// list, as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.nodes.shrink_to_fit();
+        for adjacency in self.nodes.values_mut() {
+            adjacency.shrink_to_fit();
+        }
+        self.edges.shrink_to_fit();
+    }
+
+    /// Use their natural order to map the node pair (a, b) to a canonical edge id.
+    #[inline]
+    fn edge_key(a: N, b: N) -> (N, N) {
+        if Ty::is_directed() || a <= b {
+            (a, b)
+        } else {
+            (b, a)
+        }
+    }
+
+    /// Whether the graph has directed edges.
+    pub fn is_directed(&self) -> bool {
+        Ty::is_directed()
+    }
+
+    /// Create a new `GraphMap` from an iterable of edges.
+    ///
+    /// Node values are taken directly from the list.
+    /// Edge weights `E` may either be specified in the list,
+    /// or they are filled with default values.
+    ///
+    /// Nodes are inserted automatically to match the edges.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// // Create a new undirected GraphMap.
+    /// // Use a type hint to have `()` be the edge weight type.
+    /// let gr = UnGraphMap::<_, ()>::from_edges(&[
+    ///     (0, 1), (0, 2), (0, 3),
+    ///     (1, 2), (1, 3),
+    ///     (2, 3),
+    /// ]);
+    /// ```
+    pub fn from_edges<I>(iterable: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: IntoWeightedEdge<E, NodeId = N>,
+        S: Default,
+    {
+        Self::from_iter(iterable)
+    }
+
+    /// Create a new `GraphMap` from an iterable of edges, computing each edge
+    /// weight from its endpoints with `weight_fn`.
+    ///
+    /// Nodes are inserted automatically to match the edges. `weight_fn` is
+    /// called with the endpoints in canonical order: as given for a directed
+    /// graph, and with the smaller node first for an undirected graph. If an
+    /// edge is listed more than once, the last weight is kept.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let gr = UnGraphMap::from_edges_with(vec![(3, 1), (1, 0)], |a: i32, b: i32| b - a);
+    /// assert_eq!(gr[(1, 3)], 2);
+    /// assert_eq!(gr[(0, 1)], 1);
+    /// ```
+    pub fn from_edges_with<I, F>(iterable: I, mut weight_fn: F) -> Self
+    where
+        I: IntoIterator<Item = (N, N)>,
+        F: FnMut(N, N) -> E,
+        S: Default,
+    {
+        let iter = iterable.into_iter();
+        let (low, _) = iter.size_hint();
+        let mut gr = Self::with_capacity(0, low);
+        for (a, b) in iter {
+            let (a, b) = Self::edge_key(a, b);
+            gr.add_edge(a, b, weight_fn(a, b));
+        }
+        gr
+    }
+
+    /// Create a new `GraphMap` from an iterable of edges that may contain
+    /// duplicates, combining the weights of repeated edges with `reduce`.
+    ///
+    /// `reduce` is called with the weight so far and the weight of the
+    /// repeated edge. For an undirected graph, `(a, b)` and `(b, a)` are the
+    /// same edge. Nodes are inserted automatically to match the edges.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let gr = UnGraphMap::from_edges_dedup(vec![(1, 2, 5), (2, 1, 3)], |w, other| *w += other);
+    /// assert_eq!(gr.edge_count(), 1);
+    /// assert_eq!(gr[(1, 2)], 8);
+    /// ```
+    pub fn from_edges_dedup<I, F>(iterable: I, reduce: F) -> Self
+    where
+        I: IntoIterator,
+        I::Item: IntoWeightedEdge<E, NodeId = N>,
+        F: FnMut(&mut E, E),
+        S: Default,
+    {
+        let mut gr = Self::new();
+        gr.extend_with(iterable, reduce);
+        gr
+    }
+
+    /// Create a new `GraphMap` from an iterable of edges that are sorted by
+    /// their canonical endpoints: as given for a directed graph, and with the
+    /// smaller node first for an undirected graph.
+    ///
+    /// Edges **must** be sorted in strictly increasing order, so that no edge
+    /// is repeated. Nodes are inserted in order of first appearance, and
+    /// edges keep the input order.
+    ///
+    /// Return `Err` at the first edge that is out of order or repeated.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let gr = UnGraphMap::from_sorted_edges(vec![(0, 1, 'a'), (2, 0, 'b'), (1, 2, 'c')]).unwrap();
+    /// assert_eq!(gr.edge_count(), 3);
+    /// assert!(UnGraphMap::from_sorted_edges(vec![(1, 2, 'a'), (0, 2, 'b')]).is_err());
+    /// ```
+    pub fn from_sorted_edges<I>(iterable: I) -> Result<Self, UnsortedError<N>>
+    where
+        I: IntoIterator<Item = (N, N, E)>,
+        S: Default,
+    {
+        let iter = iterable.into_iter();
+        let (low, _) = iter.size_hint();
+        let mut gr = Self::with_capacity(0, low);
+        let mut last = None;
+        for (a, b, weight) in iter {
+            let key = Self::edge_key(a, b);
+            match last {
+                Some(last) if key <= last => {
+                    return Err(UnsortedError {
+                        first_error: (a, b),
+                    });
+                }
+                _ => last = Some(key),
+            }
+            gr.edges.insert(key, weight);
+            link_nodes(&mut gr.nodes, key.0, key.1);
+        }
+        Ok(gr)
+    }
+
+    /// Create a new `GraphMap` from an iterable of nodes, without any edges.
+    ///
+    /// Duplicate nodes are inserted only once.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let gr = DiGraphMap::<_, ()>::from_nodes(0..5);
+    /// assert_eq!(gr.node_count(), 5);
+    /// assert_eq!(gr.edge_count(), 0);
+    /// ```
+    pub fn from_nodes<I>(iterable: I) -> Self
+    where
+        I: IntoIterator<Item = N>,
+        S: Default,
+    {
+        let mut gr = Self::new();
+        gr.add_nodes_from(iterable);
+        gr
+    }
+
+    /// Create a new `GraphMap` from an iterable of adjacency lists: pairs of
+    /// a node and the nodes it has an edge to, with weight `E::default()`.
+    ///
+    /// Nodes that only appear as neighbors are inserted automatically. For an
+    /// undirected graph, an edge listed under both of its endpoints is
+    /// inserted once.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let gr = UnGraphMap::<_, ()>::from_adjacency_lists(vec![
+    ///     (0, vec![1, 2]),
+    ///     (1, vec![0]),
+    ///     (3, vec![]),
+    /// ]);
+    /// assert_eq!(gr.node_count(), 4);
+    /// assert_eq!(gr.edge_count(), 2);
+    /// ```
+    pub fn from_adjacency_lists<I, J>(lists: I) -> Self
+    where
+        I: IntoIterator<Item = (N, J)>,
+        J: IntoIterator<Item = N>,
+        E: Default,
+        S: Default,
+    {
+        let mut gr = Self::new();
+        for (a, neighbors) in lists {
+            gr.add_node(a);
+            for b in neighbors {
+                gr.add_edge_unweighted(a, b);
+            }
+        }
+        gr
+    }
+
+    /// Create a random graph over `nodes`, in the *G(n, p)* model: each
+    /// possible edge between two distinct nodes is added independently with
+    /// probability `p`, with weight `E::default()`.
+    ///
+    /// For a directed graph, both directions are considered separately. No
+    /// self loops are added.
+    ///
+    /// Requires crate feature `"rand"`.
+    ///
+    /// **Panics** if `p` is not in the range `[0, 1]`.
+    #[cfg(feature = "rand")]
+    pub fn gen_gnp<R>(nodes: &[N], p: f64, rng: &mut R) -> Self
+    where
+        R: Rng,
+        E: Default,
+        S: Default,
+    {
+        let mut gr = Self::with_capacity(nodes.len(), 0);
+        gr.add_nodes_from(nodes.iter().cloned());
+        for (i, &a) in nodes.iter().enumerate() {
+            let targets = if Ty::is_directed() {
+                nodes
+            } else {
+                &nodes[i + 1..]
+            };
+            for &b in targets {
+                if a != b && rng.gen_bool(p) {
+                    gr.add_edge(a, b, E::default());
+                }
+            }
+        }
+        gr
+    }
+
+    /// Sort the nodes of the graph, so that `.nodes()` and the other node
+    /// iterators produce them in ascending order.
+    ///
+    /// The edges and the adjacency lists are not reordered.
+    ///
+    /// Computes in **O(|V| log |V|)** time.
+    pub fn sort_nodes(&mut self) {
+        self.nodes.sort_keys();
+    }
+
+    /// Sort the edges of the graph by their endpoints, so that `.all_edges()`
+    /// produces them in ascending order.
+    ///
+    /// Computes in **O(|E| log |E|)** time.
+    pub fn sort_edges(&mut self) {
+        self.edges.sort_keys();
+    }
+
+    /// Return the number of nodes in the graph.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Return the number of edges in the graph.
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Remove all nodes and edges
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.edges.clear();
+    }
+
+    /// Remove all edges, keeping the nodes.
+    pub fn clear_edges(&mut self) {
+        self.edges.clear();
+        for adjacency in self.nodes.values_mut() {
+            adjacency.clear();
+        }
+    }
+
+    /// Add node `n` to the graph.
+    pub fn add_node(&mut self, n: N) -> N {
+        self.nodes.entry(n).or_insert(Vec::new());
+        n
+    }
+
+    /// Add each node of `iterable` to the graph.
+    ///
+    /// Nodes that are already in the graph are left unchanged.
+    pub fn add_nodes_from<I>(&mut self, iterable: I)
+    where
+        I: IntoIterator<Item = N>,
+    {
+        let iter = iterable.into_iter();
+        let (low, _) = iter.size_hint();
+        self.nodes.reserve(low);
+        for n in iter {
+            self.add_node(n);
+        }
+    }
+
+    /// Return `true` if node `n` was removed.
+    ///
+    /// The weights of the edges of `n` are dropped; use
+    /// `remove_node_with_edges` to get them back.
+    ///
+    /// Computes in **O(V)** time, due to the removal of edges with other nodes.
+    pub fn remove_node(&mut self, n: N) -> bool {
+        self.remove_node_and_edges(n, |_, _, _| {})
+    }
+
+    /// Remove node `n` and its edges, like `remove_node`, but keep the
+    /// iteration order of the remaining nodes, edges and adjacency lists.
+    ///
+    /// Return `true` if the node did exist.
+    ///
+    /// Computes in **O(|V| + |E|)** time, due to shifting the nodes and edges
+    /// that come after it.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let mut g = UnGraphMap::<_, ()>::from_nodes(0..5);
+    /// g.remove_node_stable(2);
+    /// assert_eq!(g.nodes().collect::<Vec<_>>(), vec![0, 1, 3, 4]);
+    /// ```
+    pub fn remove_node_stable(&mut self, n: N) -> bool {
+        let links = match self.nodes.shift_remove(&n) {
+            None => return false,
+            Some(sus) => sus,
+        };
+        for (succ, _) in links {
+            if let Some(sus) = self.nodes.get_mut(&succ) {
+                sus.retain(|&(other, _)| other != n);
+            }
+        }
+        self.edges.retain(|&(a, b), _| a != n && b != n);
+        true
+    }
+
+    /// Remove node `n` and return the edges that were connected to it, as
+    /// pairs of the other endpoint and the edge weight, or `None` if the node
+    /// did not exist.
+    ///
+    /// A self loop on `n` is returned once, paired with `n` itself.
+    ///
+    /// Computes in **O(V)** time, due to the removal of edges with other nodes.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let mut g = DiGraphMap::<_, i32>::from_edges(&[(1, 2, 12), (2, 2, 22), (3, 2, 32)]);
+    /// let mut edges = g.remove_node_with_edges(2).unwrap();
+    /// edges.sort();
+    /// assert_eq!(edges, vec![(1, 12), (2, 22), (3, 32)]);
+    /// assert_eq!(g.remove_node_with_edges(2), None);
+    /// ```
+    pub fn remove_node_with_edges(&mut self, n: N) -> Option<Vec<(N, E)>> {
+        let mut removed = Vec::new();
+        if self.remove_node_and_edges(n, |other, _, weight| removed.push((other, weight))) {
+            Some(removed)
+        } else {
+            None
+        }
+    }
+
+    /// Remove node `n`, calling `f` with the other endpoint, the direction
+    /// and the weight of each edge connected to it.
+    ///
+    /// Return `true` if the node did exist.
+    fn remove_node_and_edges<F>(&mut self, n: N, mut f: F) -> bool
+    where
+        F: FnMut(N, Direction, E),
+    {
+        let links = match self.nodes.swap_remove(&n) {
+            None => return false,
+            Some(sus) => sus,
+        };
+        for (succ, dir) in links {
+            let edge = if dir == CompactDirection::Outgoing {
+                Self::edge_key(n, succ)
+            } else {
+                Self::edge_key(succ, n)
+            };
+            // remove all successor links
+            self.remove_single_edge(&succ, &n, dir.opposite());
+            // Remove all edge values
+            if let Some(weight) = self.edges.swap_remove(&edge) {
+                f(succ, dir.direction(), weight);
+            }
+        }
+        true
+    }
+
+    /// Contract node `remove` into node `keep`: move every edge of `remove`
+    /// over to `keep`, then remove `remove`.
+    ///
+    /// When `keep` already has an edge to the same other endpoint, in the
+    /// same direction, the weights are combined with `merge_weight`, called
+    /// with the weight of `keep`'s edge and the weight of the moved edge.
+    /// Edges between `keep` and `remove` are dropped, and so is a self loop of
+    /// `remove`, unless `keep` has a self loop to merge it into.
+    ///
+    /// Inserts node `keep` if it isn't already part of the graph. Does
+    /// nothing if `remove` doesn't exist or is equal to `keep`.
+    ///
+    /// Computes in **O(V)** time, due to the removal of edges with other nodes.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let mut g = UnGraphMap::<_, _>::from_edges(&[(0, 1, 1), (1, 2, 2), (0, 2, 3)]);
+    /// g.contract_nodes(0, 1, |w, other| *w += other);
+    /// assert_eq!(g.node_count(), 2);
+    /// assert_eq!(g.edge_count(), 1);
+    /// assert_eq!(g.edge_weight(0, 2), Some(&5));
+    /// ```
+    pub fn contract_nodes<F>(&mut self, keep: N, remove: N, mut merge_weight: F)
+    where
+        F: FnMut(&mut E, E),
+    {
+        if keep == remove || !self.contains_node(remove) {
+            return;
+        }
+        self.add_node(keep);
+        let mut moved = Vec::new();
+        self.remove_node_and_edges(remove, |other, dir, weight| {
+            moved.push((other, dir, weight))
+        });
+        for (other, dir, weight) in moved {
+            let (a, b) = if other == keep {
+                continue;
+            } else if other == remove {
+                if let Some(old) = self.edges.get_mut(&(keep, keep)) {
+                    merge_weight(old, weight);
+                }
+                continue;
+            } else if dir == Outgoing {
+                (keep, other)
+            } else {
+                (other, keep)
+            };
+            match self.edges.entry(Self::edge_key(a, b)) {
+                Occupied(mut ent) => merge_weight(ent.get_mut(), weight),
+                Vacant(ent) => {
+                    ent.insert(weight);
+                    link_nodes(&mut self.nodes, a, b);
+                }
+            }
+        }
+    }
+
+    /// Swap the edges of nodes `a` and `b`, so that every edge that was
+    /// connected to `a` is now connected to `b`, and vice versa.
+    ///
+    /// The edge between `a` and `b`, if any, is reversed, and self loops are
+    /// swapped too. Both nodes keep their place in the iteration order of
+    /// `.nodes()`.
+    ///
+    /// Return `false` if either node doesn't exist.
+    ///
+    /// Computes in **O(V)** time, due to the removal of edges with other nodes.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let mut g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2)]);
+    /// assert!(g.swap_node_keys(0, 2));
+    /// assert!(g.contains_edge(2, 1));
+    /// assert!(g.contains_edge(1, 0));
+    /// assert_eq!(g.edge_count(), 2);
+    /// ```
+    pub fn swap_node_keys(&mut self, a: N, b: N) -> bool {
+        if !self.contains_node(a) || !self.contains_node(b) {
+            return false;
+        }
+        if a == b {
+            return true;
+        }
+        let mut keys = Vec::new();
+        for &(n, other) in &[(a, b), (b, a)] {
+            for &(m, dir) in &self.nodes[&n] {
+                // the edges between `a` and `b` are collected from `a` only
+                if n == b && m == other {
+                    continue;
+                }
+                keys.push(if dir == Outgoing { (n, m) } else { (m, n) });
+            }
+        }
+        let swap = |n| {
+            if n == a {
+                b
+            } else if n == b {
+                a
+            } else {
+                n
+            }
+        };
+        let mut moved = Vec::with_capacity(keys.len());
+        for (x, y) in keys {
+            if let Some(weight) = self.remove_edge(x, y) {
+                moved.push((swap(x), swap(y), weight));
+            }
+        }
+        for (x, y, weight) in moved {
+            self.add_edge(x, y, weight);
+        }
+        true
+    }
+
+    /// Replace both endpoints of every edge with the node returned by `remap`,
+    /// and rebuild the adjacency lists.
+    ///
+    /// Edges that end up with the same endpoints are merged into one, keeping
+    /// the weight of the last of them in the iteration order of
+    /// `.all_edges()`. For an undirected graph, the endpoints are put back in
+    /// canonical order.
+    ///
+    /// No nodes are removed, even if they no longer have any edges. Nodes
+    /// returned by `remap` are inserted if they aren't already part of the
+    /// graph.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let mut g = UnGraphMap::<_, _>::from_edges(&[(0, 1, 'a'), (1, 2, 'b'), (0, 2, 'c')]);
+    /// g.redirect_edges(|n| if n == 1 { 0 } else { n });
+    /// assert_eq!(g.edge_count(), 2);
+    /// assert_eq!(g[(0, 0)], 'a');
+    /// assert_eq!(g[(0, 2)], 'c');
+    /// assert_eq!(g.neighbors(1).count(), 0);
+    /// ```
+    pub fn redirect_edges<F>(&mut self, mut remap: F)
+    where
+        F: FnMut(N) -> N,
+        S: Clone,
+    {
+        let new_edges =
+            IndexMap::with_capacity_and_hasher(self.edges.len(), self.nodes.hasher().clone());
+        let edges = mem::replace(&mut self.edges, new_edges);
+        for links in self.nodes.values_mut() {
+            links.clear();
+        }
+        for ((a, b), weight) in edges {
+            self.add_edge(remap(a), remap(b), weight);
+        }
+    }
+
+    /// Keep all nodes that return `true` from the `visit` closure,
+    /// remove the others.
+    ///
+    /// `visit` is provided a reference to the graph and a node.
+    /// Edges connected to a removed node are removed as well.
+    ///
+    /// Nodes are visited in reverse iteration order.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let mut g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+    /// g.retain_nodes(|_, n| n != 1);
+    /// assert_eq!(g.node_count(), 3);
+    /// assert_eq!(g.edge_count(), 1);
+    /// ```
+    pub fn retain_nodes<F>(&mut self, mut visit: F)
+    where
+        F: FnMut(&Self, N) -> bool,
+    {
+        // iterate in reverse, so that swap_remove only moves nodes that were
+        // already visited
+        for index in (0..self.node_count()).rev() {
+            let (&n, _) = self.nodes.get_index(index).unwrap();
+            if !visit(self, n) {
+                self.remove_node(n);
+            }
+        }
+    }
+
+    /// Remove all nodes for which `predicate` returns `true`, together with
+    /// their edges, and return them in the iteration order of `.nodes()`.
+    ///
+    /// The remaining nodes and edges keep their relative iteration order.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let mut g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4)]);
+    /// assert_eq!(g.drain_nodes(|n| n % 2 == 1), vec![1, 3]);
+    /// assert_eq!(g.nodes().collect::<Vec<_>>(), vec![0, 2, 4]);
+    /// assert_eq!(g.edge_count(), 0);
+    /// ```
+    pub fn drain_nodes<F>(&mut self, mut predicate: F) -> Vec<N>
+    where
+        F: FnMut(N) -> bool,
+    {
+        let mut removed = Vec::new();
+        self.nodes.retain(|&n, _| {
+            let remove = predicate(n);
+            if remove {
+                removed.push(n);
+            }
+            !remove
+        });
+        if removed.is_empty() {
+            return removed;
+        }
+        let nodes = &self.nodes;
+        self.edges
+            .retain(|(a, b), _| nodes.contains_key(a) && nodes.contains_key(b));
+        let edges = &self.edges;
+        for (&n, links) in self.nodes.iter_mut() {
+            links.retain(|&(m, dir)| {
+                let edge = if dir == CompactDirection::Outgoing {
+                    Self::edge_key(n, m)
+                } else {
+                    Self::edge_key(m, n)
+                };
+                edges.contains_key(&edge)
+            });
+        }
+        removed
+    }
+
+    /// Keep all edges that return `true` from the `visit` closure,
+    /// remove the others.
+    ///
+    /// `visit` is provided a reference to the graph and the endpoints of an
+    /// edge. Nodes are never removed.
+    ///
+    /// Edges are visited in reverse iteration order.
+    pub fn retain_edges<F>(&mut self, mut visit: F)
+    where
+        F: FnMut(&Self, N, N) -> bool,
+    {
+        for index in (0..self.edge_count()).rev() {
+            let (&(a, b), _) = self.edges.get_index(index).unwrap();
+            if !visit(self, a, b) {
+                self.remove_edge(a, b);
+            }
+        }
+    }
+
+    /// Keep all edges that return `true` from the `keep` closure, remove the
+    /// others, like `retain_edges`.
+    ///
+    /// `keep` is provided the endpoints of an edge and a mutable reference to
+    /// its weight, so weights can be updated in the same pass. Nodes are
+    /// never removed, and the remaining edges keep their iteration order.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let mut g = DiGraphMap::<_, u32>::from_edges(&[(0, 1, 4), (1, 2, 1), (2, 0, 6)]);
+    /// g.retain_edges_mut(|_, _, w| {
+    ///     *w /= 2;
+    ///     *w > 0
+    /// });
+    /// assert_eq!(g.all_edges().collect::<Vec<_>>(), vec![(0, 1, &2), (2, 0, &3)]);
+    /// assert_eq!(g.neighbors(1).count(), 0);
+    /// ```
+    pub fn retain_edges_mut<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(N, N, &mut E) -> bool,
+    {
+        let edge_count = self.edge_count();
+        self.edges.retain(|&(a, b), weight| keep(a, b, weight));
+        if self.edge_count() == edge_count {
+            return;
+        }
+        let edges = &self.edges;
+        for (&n, links) in self.nodes.iter_mut() {
+            links.retain(|&(m, dir)| {
+                let edge = if dir == CompactDirection::Outgoing {
+                    Self::edge_key(n, m)
+                } else {
+                    Self::edge_key(m, n)
+                };
+                edges.contains_key(&edge)
+            });
+        }
+    }
+
+    /// Move the nodes for which `predicate` returns `true` into a new graph,
+    /// together with the edges between them.
+    ///
+    /// Edges with one endpoint in each graph are dropped from both. Nodes
+    /// and edges keep their relative iteration order.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let mut g = UnGraphMap::<_, ()>::from_edges(&[(0, 2), (2, 4), (1, 3), (3, 4)]);
+    /// let odd = g.split_off(|n| n % 2 == 1);
+    /// assert_eq!(g.nodes().collect::<Vec<_>>(), vec![0, 2, 4]);
+    /// assert_eq!(odd.nodes().collect::<Vec<_>>(), vec![1, 3]);
+    /// assert_eq!(g.edge_count(), 2);
+    /// assert_eq!(odd.edge_count(), 1);
+    /// ```
+    pub fn split_off<F>(&mut self, mut predicate: F) -> Self
+    where
+        F: FnMut(N) -> bool,
+        S: Clone,
+    {
+        let mut other = GraphMap::with_hasher(self.nodes.hasher().clone());
+        for &n in self.nodes.keys() {
+            if predicate(n) {
+                other.add_node(n);
+            }
+        }
+        let edges = mem::replace(
+            &mut self.edges,
+            IndexMap::with_hasher(self.nodes.hasher().clone()),
+        );
+        self.nodes.retain(|n, links| {
+            links.clear();
+            !other.contains_node(*n)
+        });
+        for ((a, b), weight) in edges {
+            match (other.contains_node(a), other.contains_node(b)) {
+                (true, true) => {
+                    other.add_edge(a, b, weight);
+                }
+                (false, false) => {
+                    self.add_edge(a, b, weight);
+                }
+                _ => {}
+            }
+        }
+        other
+    }
+
+    /// Return `true` if the node is contained in the graph.
+    pub fn contains_node(&self, n: N) -> bool {
+        self.nodes.contains_key(&n)
+    }
+
+    /// Return the node of the graph that is equal to `key`, or `None` if
+    /// there is none.
+    ///
+    /// `key` can be any borrowed form of `N`, so a graph with `&str` nodes
+    /// can be queried with a `&str` of a shorter lifetime, borrowed from a
+    /// `String`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<_, ()>::from_edges(&[("a", "b")]);
+    /// let key = String::from("b");
+    /// let b = g.get_node(key.as_str()).unwrap();
+    /// assert!(g.contains_edge("a", b));
+    /// assert_eq!(g.get_node("c"), None);
+    /// ```
+    pub fn get_node<Q>(&self, key: &Q) -> Option<N>
+    where
+        N: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.nodes.get_full(key).map(|(_, &n, _)| n)
+    }
+
+    /// Add an edge connecting `a` and `b` to the graph, with associated
+    /// data `weight`. For a directed graph, the edge is directed from `a`
+    /// to `b`.
+    ///
+    /// Inserts nodes `a` and/or `b` if they aren't already part of the graph.
+    ///
+    /// Return `None` if the edge did not previously exist, otherwise,
+    /// the associated data is updated and the old value is returned
+    /// as `Some(old_weight)`.
+    ///
+    /// ```
+    /// // Create a GraphMap with directed edges, and add one edge to it
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let mut g = DiGraphMap::new();
+    /// g.add_edge("x", "y", -1);
+    /// assert_eq!(g.node_count(), 2);
+    /// assert_eq!(g.edge_count(), 1);
+    /// assert!(g.contains_edge("x", "y"));
+    /// assert!(!g.contains_edge("y", "x"));
+    /// ```
+    pub fn add_edge(&mut self, a: N, b: N, weight: E) -> Option<E> {
+        if let old @ Some(_) = self.edges.insert(Self::edge_key(a, b), weight) {
+            old
+        } else {
+            // insert in the adjacency list if it's a new edge
+            link_nodes(&mut self.nodes, a, b);
+            None
+        }
+    }
+
+    /// Add an edge connecting `a` and `b` to the graph, with associated
+    /// data `weight`, and return a mutable reference to the stored weight.
+    ///
+    /// Inserts nodes `a` and/or `b` if they aren't already part of the graph.
+    /// If the edge already exists, its weight is replaced by `weight`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let mut g = UnGraphMap::new();
+    /// *g.add_or_update_edge(1, 2, 10) += 1;
+    /// assert_eq!(g[(1, 2)], 11);
+    /// ```
+    pub fn add_or_update_edge(&mut self, a: N, b: N, weight: E) -> &mut E {
+        match self.edges.entry(Self::edge_key(a, b)) {
+            Occupied(ent) => {
+                let old = ent.into_mut();
+                *old = weight;
+                old
+            }
+            Vacant(ent) => {
+                link_nodes(&mut self.nodes, a, b);
+                ent.insert(weight)
+            }
+        }
+    }
+
+    /// Add an edge connecting `a` and `b` to the graph, with associated
+    /// data `weight`, only if it doesn't exist yet.
+    ///
+    /// Inserts nodes `a` and/or `b` if they aren't already part of the graph.
+    ///
+    /// Return `Err` with the rejected `weight` if the edge already exists; the
+    /// stored weight is left unchanged.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let mut g = DiGraphMap::new();
+    /// assert_eq!(g.try_add_edge(1, 2, "first"), Ok(()));
+    /// assert_eq!(g.try_add_edge(1, 2, "second"), Err("second"));
+    /// assert_eq!(g[(1, 2)], "first");
+    /// ```
+    pub fn try_add_edge(&mut self, a: N, b: N, weight: E) -> Result<(), E> {
+        match self.edges.entry(Self::edge_key(a, b)) {
+            Occupied(_) => Err(weight),
+            Vacant(ent) => {
+                ent.insert(weight);
+                link_nodes(&mut self.nodes, a, b);
+                Ok(())
+            }
+        }
+    }
+
+    /// Add an edge connecting `a` and `b` with weight `E::default()`, if it
+    /// doesn't exist yet.
+    ///
+    /// Inserts nodes `a` and/or `b` if they aren't already part of the graph.
+    ///
+    /// Return `true` if the edge was added; the weight of an existing edge is
+    /// left unchanged.
+    pub fn add_edge_unweighted(&mut self, a: N, b: N) -> bool
+    where
+        E: Default,
+    {
+        if self.contains_edge(a, b) {
+            false
+        } else {
+            self.add_edge(a, b, E::default());
+            true
+        }
+    }
+
+    /// Remove edge relation from a to b
+    ///
+    /// Return `true` if it did exist.
+    fn remove_single_edge(&mut self, a: &N, b: &N, dir: Direction) -> bool {
+        match self.find_single_edge(a, b, dir) {
+            Some((sus, index)) => {
+                sus.swap_remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove edge relation from a to b, preserving the order of the
+    /// adjacency list.
+    ///
+    /// Return `true` if it did exist.
+    fn shift_remove_single_edge(&mut self, a: &N, b: &N, dir: Direction) -> bool {
+        match self.find_single_edge(a, b, dir) {
+            Some((sus, index)) => {
+                sus.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Return the adjacency list of a and the position of the edge relation
+    /// from a to b in it.
+    fn find_single_edge(
+        &mut self,
+        a: &N,
+        b: &N,
+        dir: Direction,
+    ) -> Option<(&mut Vec<(N, CompactDirection)>, usize)> {
+        let sus = self.nodes.get_mut(a)?;
+        let index = if Ty::is_directed() {
+            sus.iter()
+                .position(|elt| elt == &(*b, CompactDirection::from(dir)))
+        } else {
+            sus.iter().position(|elt| &elt.0 == b)
+        }?;
+        Some((sus, index))
+    }
+
+    /// Remove edge from `a` to `b` from the graph and return the edge weight.
+    ///
+    /// Return `None` if the edge didn't exist.
+    ///
+    /// ```
+    /// // Create a GraphMap with undirected edges, and add and remove an edge.
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let mut g = UnGraphMap::new();
+    /// g.add_edge("x", "y", -1);
+    ///
+    /// let edge_data = g.remove_edge("y", "x");
+    /// assert_eq!(edge_data, Some(-1));
+    /// assert_eq!(g.edge_count(), 0);
+    /// ```
+    pub fn remove_edge(&mut self, a: N, b: N) -> Option<E> {
+        let exist1 = self.remove_single_edge(&a, &b, Outgoing);
+        let exist2 = if a != b {
+            self.remove_single_edge(&b, &a, Incoming)
+        } else {
+            exist1
+        };
+        let weight = self.edges.remove(&Self::edge_key(a, b));
+        debug_assert!(
+            exist1 == exist2 && exist1 == weight.is_some(),
+            "GraphMap::remove_edge: adjacency lists out of sync with edge map"
+        );
+        weight
+    }
+
+    /// Remove edge from `a` to `b` from the graph and return the edge weight,
+    /// like `remove_edge`, but keep the iteration order of the remaining
+    /// edges and adjacency lists.
+    ///
+    /// Return `None` if the edge didn't exist.
+    ///
+    /// Computes in **O(|E|)** time, due to shifting the edges that come after
+    /// it.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let mut g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+    /// g.remove_edge_stable(0, 1);
+    /// assert_eq!(g.all_edges().map(|(a, b, _)| (a, b)).collect::<Vec<_>>(), vec![(1, 2), (2, 3)]);
+    /// ```
+    pub fn remove_edge_stable(&mut self, a: N, b: N) -> Option<E> {
+        let exist1 = self.shift_remove_single_edge(&a, &b, Outgoing);
+        let exist2 = if a != b {
+            self.shift_remove_single_edge(&b, &a, Incoming)
+        } else {
+            exist1
+        };
+        let weight = self.edges.shift_remove(&Self::edge_key(a, b));
+        debug_assert!(
+            exist1 == exist2 && exist1 == weight.is_some(),
+            "GraphMap::remove_edge_stable: adjacency lists out of sync with edge map"
+        );
+        weight
+    }
+
+    /// Remove every edge in `iterable` from the graph.
+    ///
+    /// Edges that don't exist are skipped. Return the number of edges that
+    /// were removed.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let mut g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+    /// assert_eq!(g.remove_edges_from(vec![(1, 0), (2, 3), (0, 3)]), 2);
+    /// assert_eq!(g.edge_count(), 1);
+    /// ```
+    pub fn remove_edges_from<I>(&mut self, iterable: I) -> usize
+    where
+        I: IntoIterator<Item = (N, N)>,
+    {
+        let mut removed = 0;
+        for (a, b) in iterable {
+            if self.edges.swap_remove(&Self::edge_key(a, b)).is_some() {
+                self.remove_single_edge(&a, &b, Outgoing);
+                if a != b {
+                    self.remove_single_edge(&b, &a, Incoming);
+                }
+                removed += 1;
+            }
+        }
+        removed
+    }
+
+    /// Return the entry of the edge connecting `a` and `b`, for in-place
+    /// insertion or modification of its weight.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let mut g = UnGraphMap::<_, u32>::new();
+    /// for &(a, b) in &[("x", "y"), ("y", "z"), ("y", "x")] {
+    ///     *g.edge_entry(a, b).or_insert(0) += 1;
+    /// }
+    /// assert_eq!(g[("x", "y")], 2);
+    /// assert_eq!(g[("y", "z")], 1);
+    /// ```
+    pub fn edge_entry(&mut self, a: N, b: N) -> EdgeEntry<'_, N, E, Ty, S> {
+        EdgeEntry { graph: self, a, b }
+    }
+
+    /// Return `true` if the edge connecting `a` with `b` is contained in the graph.
+    pub fn contains_edge(&self, a: N, b: N) -> bool {
+        self.edges.contains_key(&Self::edge_key(a, b))
+    }
+
+    /// Return `true` if an edge connects `a` and `b` in either direction.
+    ///
+    /// For an undirected graph this is the same as `contains_edge`.
+    pub fn contains_edge_undirected(&self, a: N, b: N) -> bool {
+        self.contains_edge(a, b) || (Ty::is_directed() && self.contains_edge(b, a))
+    }
+
+    /// Return a reference to the weight of an edge connecting `a` and `b` in
+    /// either direction, or `None` if there is no such edge.
+    ///
+    /// If a directed graph has both `a -> b` and `b -> a`, the weight of
+    /// `a -> b` is returned. For an undirected graph this is the same as
+    /// `edge_weight`.
+    pub fn edge_weight_undirected(&self, a: N, b: N) -> Option<&E> {
+        match self.edge_weight(a, b) {
+            None if Ty::is_directed() => self.edge_weight(b, a),
+            weight => weight,
+        }
+    }
+
+    /// Check the internal consistency of the graph: that every adjacency
+    /// list entry has a matching edge and a matching entry in the adjacency
+    /// list of the other endpoint, that every edge has its adjacency list
+    /// entries, and that no entry is duplicated.
+    ///
+    /// Return a description of the first problem found, if any. This is
+    /// meant for tests; it computes in **O(|V| + |E|)** time (average) and
+    /// allocates.
+    pub fn check_invariants(&self) -> Result<(), String>
+    where
+        N: fmt::Debug,
+    {
+        let mut entries = 0;
+        let mut seen = HashSet::new();
+        for (&a, neighbors) in &self.nodes {
+            seen.clear();
+            for &(b, dir) in neighbors {
+                if !seen.insert((b, dir == CompactDirection::Outgoing)) {
+                    return Err(format!("duplicate adjacency {:?} of {:?}", (b, dir), a));
+                }
+                let (source, target) = match dir {
+                    CompactDirection::Outgoing => (a, b),
+                    CompactDirection::Incoming => (b, a),
+                };
+                if !self.edges.contains_key(&Self::edge_key(source, target)) {
+                    return Err(format!(
+                        "adjacency {:?} of {:?} has no edge {:?}",
+                        (b, dir),
+                        a,
+                        (source, target)
+                    ));
+                }
+                let reverse = match self.nodes.get(&b) {
+                    None => return Err(format!("adjacency {:?} of {:?} is not a node", b, a)),
+                    Some(reverse) => reverse,
+                };
+                let opposite = CompactDirection::from(dir.opposite());
+                if a != b && !reverse.iter().any(|&(n, d)| n == a && d == opposite) {
+                    return Err(format!(
+                        "adjacency {:?} of {:?} has no reverse entry",
+                        (b, dir),
+                        a
+                    ));
+                }
+                if a == b && dir == CompactDirection::Incoming {
+                    return Err(format!("self loop of {:?} has an incoming entry", a));
+                }
+                entries += 1;
+            }
+        }
+        let self_loops = self.edges.keys().filter(|&&(a, b)| a == b).count();
+        if entries != 2 * self.edges.len() - self_loops {
+            return Err(format!(
+                "{} adjacency entries for {} edges and {} self loops",
+                entries,
+                self.edges.len(),
+                self_loops
+            ));
+        }
+        Ok(())
+    }
+
+    /// Return an iterator over the nodes of the graph.
+    ///
+    /// Iterator element type is `N`.
+    pub fn nodes(&self) -> Nodes<N> {
+        Nodes {
+            iter: self.nodes.keys().cloned(),
+        }
+    }
+
+    /// Return the smallest and the largest node of the graph, or `None` if
+    /// the graph has no nodes.
+    ///
+    /// Computes in **O(|V|)** time.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let g = UnGraphMap::<_, ()>::from_edges(&[(3, 7), (1, 9)]);
+    /// assert_eq!(g.node_bounds(), Some((1, 9)));
+    /// ```
+    pub fn node_bounds(&self) -> Option<(N, N)> {
+        let mut nodes = self.nodes.keys();
+        let &first = nodes.next()?;
+        Some(nodes.fold((first, first), |(min, max), &n| {
+            (cmp::min(min, n), cmp::max(max, n))
+        }))
+    }
+
+    /// Return an iterator over the nodes of the graph, each paired with an
+    /// iterator over its raw adjacency list, like `adjacencies`.
+    ///
+    /// Nodes are in the iteration order of `.nodes()`.<br>
+    /// Iterator element type is `(N, Adjacencies<N>)`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    /// use petgraph::Outgoing;
+    ///
+    /// let g = DiGraphMap::<_, ()>::from_edges(&[(1, 2), (2, 3)]);
+    /// let mut edges = Vec::new();
+    /// for (a, adjacencies) in g.node_adjacencies() {
+    ///     for (b, dir) in adjacencies {
+    ///         if dir == Outgoing {
+    ///             edges.push((a, b));
+    ///         }
+    ///     }
+    /// }
+    /// assert_eq!(edges, vec![(1, 2), (2, 3)]);
+    /// ```
+    pub fn node_adjacencies(&self) -> NodeAdjacencies<'_, N> {
+        NodeAdjacencies {
+            iter: self.nodes.iter(),
+        }
+    }
+
+    /// Return an iterator of all nodes with an edge starting from `a`.
+    ///
+    /// - `Directed`: Outgoing edges from `a`.
+    /// - `Undirected`: All edges from or to `a`.
+    ///
+    /// Produces an empty iterator if the node doesn't exist.<br>
+    /// Iterator element type is `N`.
+    pub fn neighbors(&self, a: N) -> Neighbors<N, Ty> {
+        Neighbors {
+            iter: match self.nodes.get(&a) {
+                Some(neigh) => neigh.iter(),
+                None => [].iter(),
+            },
+            ty: self.ty,
+        }
+    }
+
+    /// Return an iterator of all neighbors that have an edge between them and
+    /// `a`, in the specified direction.
+    /// If the graph's edges are undirected, this is equivalent to *.neighbors(a)*.
+    ///
+    /// - `Directed`, `Outgoing`: All edges from `a`.
+    /// - `Directed`, `Incoming`: All edges to `a`.
+    /// - `Undirected`: All edges from or to `a`.
+    ///
+    /// Produces an empty iterator if the node doesn't exist.<br>
+    /// Iterator element type is `N`.
+    pub fn neighbors_directed(&self, a: N, dir: Direction) -> NeighborsDirected<N, Ty> {
+        NeighborsDirected {
+            iter: match self.nodes.get(&a) {
+                Some(neigh) => neigh.iter(),
+                None => [].iter(),
+            },
+            start_node: a,
+            dir,
+            ty: self.ty,
+        }
+    }
+
+    /// Return an iterator over the raw adjacency list of `n`, or `None` if the
+    /// node doesn't exist.
+    ///
+    /// Each entry is a neighbor tagged with the direction of the edge as seen
+    /// from `n`. In a directed graph, a neighbor connected in both directions
+    /// appears twice, and a self loop appears once, as `(n, Outgoing)`. In an
+    /// undirected graph the direction only records which endpoint was given
+    /// first when the edge was added.
+    ///
+    /// Entries are in insertion order, except that removing an edge may move
+    /// the last entry into its place.<br>
+    /// Iterator element type is `(N, Direction)`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    /// use petgraph::{Incoming, Outgoing};
+    ///
+    /// let g = DiGraphMap::<_, ()>::from_edges(&[(1, 2), (2, 1), (1, 1)]);
+    /// let adjacencies = g.adjacencies(1).unwrap().collect::<Vec<_>>();
+    /// assert_eq!(adjacencies, vec![(2, Outgoing), (2, Incoming), (1, Outgoing)]);
+    /// assert!(g.adjacencies(3).is_none());
+    /// ```
+    pub fn adjacencies(&self, n: N) -> Option<Adjacencies<'_, N>> {
+        self.nodes
+            .get(&n)
+            .map(|neigh| Adjacencies { iter: neigh.iter() })
+    }
+
+    /// Return an iterator of all neighbors of `a`, each tagged with the
+    /// direction of the edge as seen from `a`.
+    ///
+    /// This is mainly useful for directed graphs, to visit both the incoming
+    /// and outgoing neighbors in one pass; a neighbor connected in both
+    /// directions appears twice, and a self loop appears once, as
+    /// `(a, Outgoing)`. In an undirected graph the directions carry no
+    /// meaning.
+    ///
+    /// Produces an empty iterator if the node doesn't exist.<br>
+    /// Iterator element type is `(N, Direction)`.
+    pub fn neighbors_tagged(&self, a: N) -> Adjacencies<'_, N> {
+        Adjacencies {
+            iter: match self.nodes.get(&a) {
+                Some(neigh) => neigh.iter(),
+                None => [].iter(),
+            },
+        }
+    }
+
+    /// Return the number of edges connected to `n`.
+    ///
+    /// - `Directed`: The sum of the in and out degree of `n`, so a self loop
+    ///   counts twice.
+    /// - `Undirected`: The number of edges from or to `n`; a self loop counts
+    ///   once.
+    ///
+    /// Return `0` if the node doesn't exist.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (2, 1), (1, 3)]);
+    /// assert_eq!(g.in_degree(1), 2);
+    /// assert_eq!(g.out_degree(1), 1);
+    /// assert_eq!(g.degree(1), 3);
+    /// ```
+    pub fn degree(&self, n: N) -> usize {
+        if Ty::is_directed() {
+            self.in_degree(n) + self.out_degree(n)
+        } else {
+            self.nodes.get(&n).map_or(0, |links| links.len())
+        }
+    }
+
+    /// Return the number of edges to `n`.
+    ///
+    /// A self loop counts as an edge to `n`. For an undirected graph this is
+    /// equal to the degree.
+    ///
+    /// Return `0` if the node doesn't exist.
+    pub fn in_degree(&self, n: N) -> usize {
+        self.neighbors_directed(n, Incoming).count()
+    }
+
+    /// Return the number of edges from `n`.
+    ///
+    /// A self loop counts as an edge from `n`. For an undirected graph this
+    /// is equal to the degree.
+    ///
+    /// Return `0` if the node doesn't exist.
+    pub fn out_degree(&self, n: N) -> usize {
+        self.neighbors(n).count()
+    }
+
+    /// Return the degree of every node, sorted in descending order.
+    ///
+    /// Degrees are counted like `degree`.
+    pub fn degree_sequence(&self) -> Vec<usize> {
+        let mut degrees = self
+            .nodes
+            .keys()
+            .map(|&n| self.degree(n))
+            .collect::<Vec<_>>();
+        degrees.sort_by(|a, b| b.cmp(a));
+        degrees
+    }
+
+    /// Return the edge density of the graph: the number of edges divided by
+    /// the number of edges of a complete graph on the same nodes.
+    ///
+    /// The complete graph has an edge between every pair of distinct nodes,
+    /// in both directions for a directed graph: `|V| (|V| - 1)` edges, or
+    /// half as many if undirected. Self loops count as edges, so a graph with
+    /// self loops can have a density greater than `1.0`.
+    ///
+    /// Return `0.0` if the graph has less than two nodes.
+    pub fn density(&self) -> f64 {
+        let n = self.node_count() as f64;
+        if self.node_count() < 2 {
+            return 0.;
+        }
+        let max_edges = if Ty::is_directed() {
+            n * (n - 1.)
+        } else {
+            n * (n - 1.) / 2.
+        };
+        self.edge_count() as f64 / max_edges
+    }
+
+    /// Return the sum of all edge weights, or `E::default()` if the graph has
+    /// no edges.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let g = UnGraphMap::<_, u32>::from_edges(&[(0, 1, 1), (1, 2, 5), (2, 0, 3)]);
+    /// assert_eq!(g.total_edge_weight(), 9);
+    /// ```
+    pub fn total_edge_weight(&self) -> E
+    where
+        E: Default + Add<Output = E> + Clone,
+    {
+        self.edges
+            .values()
+            .fold(E::default(), |total, weight| total + weight.clone())
+    }
+
+    /// Return the edge with the smallest weight, or `None` if the graph has
+    /// no edges.
+    ///
+    /// Of several edges with the same weight, the first one in the iteration
+    /// order of `.all_edges()` is returned. Weights that can't be compared,
+    /// like `NaN`, are never smaller than the others.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let g = UnGraphMap::<_, u32>::from_edges(&[(0, 1, 4), (1, 2, 5), (2, 0, 3)]);
+    /// assert_eq!(g.min_edge(), Some((0, 2, &3)));
+    /// ```
+    pub fn min_edge(&self) -> Option<(N, N, &E)>
+    where
+        E: PartialOrd,
+    {
+        self.extreme_edge(|weight, best| weight < best)
+    }
+
+    /// Return the edge with the largest weight, or `None` if the graph has no
+    /// edges.
+    ///
+    /// Of several edges with the same weight, the first one in the iteration
+    /// order of `.all_edges()` is returned. Weights that can't be compared,
+    /// like `NaN`, are never larger than the others.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let g = UnGraphMap::<_, u32>::from_edges(&[(0, 1, 4), (1, 2, 5), (2, 0, 3)]);
+    /// assert_eq!(g.max_edge(), Some((1, 2, &5)));
+    /// ```
+    pub fn max_edge(&self) -> Option<(N, N, &E)>
+    where
+        E: PartialOrd,
+    {
+        self.extreme_edge(|weight, best| weight > best)
+    }
+
+    /// Return the first edge whose weight is `better` than the weights of all
+    /// edges before it, skipping weights that can't be compared.
+    fn extreme_edge<F>(&self, mut better: F) -> Option<(N, N, &E)>
+    where
+        E: PartialOrd,
+        F: FnMut(&E, &E) -> bool,
+    {
+        let mut edges = self.edges.iter();
+        let (&(a, b), weight) = edges.next()?;
+        Some(edges.fold((a, b, weight), |best, (&(a, b), weight)| {
+            let incomparable = best.2.partial_cmp(best.2).is_none();
+            if better(weight, best.2) || incomparable {
+                (a, b, weight)
+            } else {
+                best
+            }
+        }))
+    }
+
+    /// Return an iterator of target nodes with an edge starting from `a`,
+    /// paired with their respective edge weights.
+    ///
+    /// - `Directed`: Outgoing edges from `a`.
+    /// - `Undirected`: All edges from or to `a`.
+    ///
+    /// Produces an empty iterator if the node doesn't exist.<br>
+    /// Iterator element type is `(N, &E)`.
+    pub fn edges(&self, from: N) -> Edges<'_, N, E, Ty, S> {
+        Edges {
+            from,
+            iter: self.neighbors(from),
+            edges: &self.edges,
+        }
+    }
+
+    /// Return an iterator of the neighbors of `from` whose connecting edge
+    /// weight satisfies `pred`.
+    ///
+    /// - `Directed`: Targets of outgoing edges from `from`.
+    /// - `Undirected`: All edges from or to `from`.
+    ///
+    /// Produces an empty iterator if the node doesn't exist.<br>
+    /// Iterator element type is `N`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<_, i32>::from_edges(&[(0, 1, 5), (0, 2, 0), (3, 0, 1)]);
+    /// assert_eq!(g.neighbors_where(0, |&capacity| capacity > 0).collect::<Vec<_>>(), vec![1]);
+    /// ```
+    pub fn neighbors_where<'a, F>(&'a self, from: N, mut pred: F) -> impl Iterator<Item = N> + 'a
+    where
+        F: FnMut(&E) -> bool + 'a,
+    {
+        self.edges(from)
+            .filter(move |&(_, _, weight)| pred(weight))
+            .map(|(_, b, _)| b)
+    }
+
+    /// Return an iterator of edges of `a` in direction `dir`, paired with
+    /// their respective edge weights.
+    ///
+    /// - `Directed`, `Outgoing`: All edges from `a`, including self loops.
+    /// - `Directed`, `Incoming`: All edges to `a`, except self loops, which
+    ///   are only reported as `Outgoing`.
+    /// - `Undirected`: All edges from or to `a`, like `edges`.
+    ///
+    /// The edge endpoints are oriented, so for a directed graph the source is
+    /// always first: `Incoming` edges are produced as `(source, a, &weight)`.
+    ///
+    /// Produces an empty iterator if the node doesn't exist.<br>
+    /// Iterator element type is `(N, N, &E)`.
+    pub fn edges_directed(&self, a: N, dir: Direction) -> EdgesDirected<'_, N, E, Ty, S> {
+        let iter = match self.nodes.get(&a) {
+            Some(neigh) => neigh.iter(),
+            None => [].iter(),
+        };
+        EdgesDirected {
+            from: a,
+            dir,
+            iter,
+            edges: &self.edges,
+            self_loops: false,
+            ty: self.ty,
+        }
+    }
+
+    /// Return the endpoints of the edge connecting `a` with `b` in the
+    /// order it is stored in, or `None` if the edge does not exist.
+    ///
+    /// For a directed graph this is `(a, b)`. For an undirected graph the
+    /// smaller node comes first, like in `.all_edges()`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let g = UnGraphMap::<_, ()>::from_edges(&[("b", "a")]);
+    /// assert_eq!(g.edge_endpoints("b", "a"), Some(("a", "b")));
+    /// assert_eq!(g.edge_endpoints("a", "c"), None);
+    /// ```
+    pub fn edge_endpoints(&self, a: N, b: N) -> Option<(N, N)> {
+        let key = Self::edge_key(a, b);
+        if self.edges.contains_key(&key) {
+            Some(key)
+        } else {
+            None
+        }
+    }
+
+    /// Return a reference to the edge weight connecting `a` with `b`, or
+    /// `None` if the edge does not exist in the graph.
+    pub fn edge_weight(&self, a: N, b: N) -> Option<&E> {
+        self.edges.get(&Self::edge_key(a, b))
+    }
+
+    /// Return a mutable reference to the edge weight connecting `a` with `b`, or
+    /// `None` if the edge does not exist in the graph.
+    pub fn edge_weight_mut(&mut self, a: N, b: N) -> Option<&mut E> {
+        self.edges.get_mut(&Self::edge_key(a, b))
+    }
+
+    /// Return a mutable reference to the edge weight connecting `a` with `b`,
+    /// adding the edge with weight `E::default()` if it does not exist.
+    ///
+    /// Inserts nodes `a` and/or `b` if they aren't already part of the graph.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let mut cooccurrences = UnGraphMap::<_, u32>::new();
+    /// for &(a, b) in &[("x", "y"), ("y", "z"), ("y", "x")] {
+    ///     *cooccurrences.edge_weight_or_default_mut(a, b) += 1;
+    /// }
+    /// assert_eq!(cooccurrences[("x", "y")], 2);
+    /// ```
+    pub fn edge_weight_or_default_mut(&mut self, a: N, b: N) -> &mut E
+    where
+        E: Default,
+    {
+        self.edge_entry(a, b).or_insert_with(E::default)
+    }
+
+    /// Return an iterator over the nodes that have a self loop, paired with
+    /// the weight of the loop, in the iteration order of `.nodes()`.
+    ///
+    /// Iterator element type is `(N, &E)`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<_, _>::from_edges(&[(0, 0, 'a'), (0, 1, 'b'), (1, 1, 'c')]);
+    /// assert_eq!(g.self_loops().collect::<Vec<_>>(), vec![(0, &'a'), (1, &'c')]);
+    /// ```
+    pub fn self_loops(&self) -> impl Iterator<Item = (N, &E)> + '_ {
+        self.nodes
+            .keys()
+            .filter_map(move |&n| self.edges.get(&(n, n)).map(|weight| (n, weight)))
+    }
+
+    /// Remove every self loop from the graph, and return the number of self
+    /// loops that were removed.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let mut g = DiGraphMap::<_, ()>::from_edges(&[(0, 0), (0, 1), (1, 1)]);
+    /// assert_eq!(g.remove_self_loops(), 2);
+    /// assert_eq!(g.edge_count(), 1);
+    /// ```
+    pub fn remove_self_loops(&mut self) -> usize {
+        let loops = self.self_loops().map(|(n, _)| n).collect::<Vec<_>>();
+        for &n in &loops {
+            self.remove_edge(n, n);
+        }
+        loops.len()
+    }
+
+    /// Return an iterator over all edges of the graph with their weight in arbitrary order.
+    ///
+    /// Iterator element type is `(N, N, &E)`
+    pub fn all_edges(&self) -> AllEdges<N, E, Ty> {
+        AllEdges {
+            inner: self.edges.iter(),
+            ty: self.ty,
+        }
+    }
+
+    /// Return an iterator over all edges of the graph in arbitrary order, with a mutable reference
+    /// to their weight.
+    ///
+    /// Iterator element type is `(N, N, &mut E)`
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let mut g = UnGraphMap::<_, f64>::from_edges(&[(0, 1, 1.5), (1, 2, 2.5)]);
+    /// for (_, _, weight) in g.all_edges_mut() {
+    ///     *weight *= 2.0;
+    /// }
+    /// assert_eq!(g.edge_weight(2, 1), Some(&5.0));
+    /// ```
+    ///
+    /// The graph can not be modified structurally while the iterator is alive:
+    ///
+    /// ```compile_fail
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let mut g = UnGraphMap::<_, f64>::from_edges(&[(0, 1, 1.5), (1, 2, 2.5)]);
+    /// for (a, _, weight) in g.all_edges_mut() {
+    ///     g.remove_node(a);
+    /// }
+    /// ```
+    pub fn all_edges_mut(&mut self) -> AllEdgesMut<N, E, Ty> {
+        AllEdgesMut {
+            inner: self.edges.iter_mut(),
+            ty: self.ty,
+        }
+    }
+
+    /// Return an iterator of target nodes with an edge starting from `from`,
+    /// paired with their respective **mutable** edge weights.
+    ///
+    /// - `Directed`: Outgoing edges from `from`.
+    /// - `Undirected`: All edges from or to `from`.
+    ///
+    /// The edges are produced as `(from, b, &mut weight)`, in the iteration
+    /// order of `.all_edges()`.
+    ///
+    /// Produces an empty iterator if the node doesn't exist.<br>
+    /// Iterator element type is `(N, N, &mut E)`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let mut g = DiGraphMap::<_, f64>::from_edges(&[(0, 1, 1.), (0, 2, 3.), (1, 2, 1.)]);
+    /// let total: f64 = g.edges(0).map(|(_, _, w)| *w).sum();
+    /// for (_, _, weight) in g.edges_mut(0) {
+    ///     *weight /= total;
+    /// }
+    /// assert_eq!(g[(0, 2)], 0.75);
+    /// assert_eq!(g[(1, 2)], 1.);
+    /// ```
+    pub fn edges_mut(&mut self, from: N) -> EdgesMut<'_, N, E, Ty> {
+        let links = match self.nodes.get(&from) {
+            Some(neigh) => &neigh[..],
+            None => &[],
+        };
+        let edges = &self.edges;
+        let mut indices: Vec<_> = links
+            .iter()
+            .filter(|&&(_, dir)| !Ty::is_directed() || dir == Outgoing)
+            .map(|&(n, _)| edges.get_index_of(&Self::edge_key(from, n)).unwrap())
+            .collect();
+        indices.sort_unstable();
+        EdgesMut {
+            from,
+            indices: indices.into_iter(),
+            position: 0,
+            inner: self.edges.iter_mut(),
+            ty: self.ty,
+        }
+    }
+
+    /// Reverse the direction of all edges.
+    ///
+    /// Edge weights follow their edges, and self loops are left as they are.
+    /// For an undirected graph this is a no-op.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    /// use petgraph::Incoming;
+    ///
+    /// let mut g = DiGraphMap::new();
+    /// g.add_edge("x", "y", 1);
+    /// g.reverse();
+    /// assert!(g.contains_edge("y", "x"));
+    /// assert_eq!(g.neighbors_directed("x", Incoming).collect::<Vec<_>>(), vec!["y"]);
+    /// ```
+    pub fn reverse(&mut self) {
+        if !Ty::is_directed() {
+            return;
+        }
+        for (&n, links) in &mut self.nodes {
+            for (other, dir) in links {
+                // self loops only have the Outgoing entry
+                if *other != n {
+                    *dir = dir.opposite().into();
+                }
+            }
+        }
+        let edges: Vec<_> = self.edges.drain(..).collect();
+        self.edges
+            .extend(edges.into_iter().map(|((a, b), weight)| ((b, a), weight)));
+    }
+
+    /// Return a copy of the graph with the direction of all edges reversed.
+    ///
+    /// See [`.reverse()`](#method.reverse).
+    pub fn reversed(&self) -> Self
+    where
+        E: Clone,
+        S: Clone,
+    {
+        let mut gr = GraphMap {
+            nodes: self.nodes.clone(),
+            edges: self.edges.clone(),
+            ty: self.ty,
+        };
+        gr.reverse();
+        gr
+    }
+
+    /// Create a new `GraphMap` by mapping nodes and edges.
+    ///
+    /// `node_map` is called once for each node, and returns its identifier
+    /// in the resulting graph. Node identifiers are the keys of the graph, so
+    /// changing them rekeys the graph; pass `|n| n` to keep them as they are.
+    ///
+    /// `edge_map` is called with the original endpoints and weight of each
+    /// edge. The resulting graph has the same structure as `self`, and its
+    /// nodes and edges are in the same order.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// **Panics** if `node_map` maps two distinct nodes to the same
+    /// identifier.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<_, f64>::from_edges(&[(0, 1, 0.5), (1, 2, 2.0)]);
+    /// let labels = g.map(|n| n, |a, b, w| format!("{}->{}: {}", a, b, w));
+    /// assert_eq!(labels[(1, 2)], "1->2: 2");
+    ///
+    /// let shifted = g.map(|n| n + 10, |_, _, &w| w);
+    /// assert_eq!(shifted[(11, 12)], 2.0);
+    /// ```
+    pub fn map<F, G, N2, E2>(&self, mut node_map: F, mut edge_map: G) -> GraphMap<N2, E2, Ty, S>
+    where
+        F: FnMut(N) -> N2,
+        G: FnMut(N, N, &E) -> E2,
+        N2: NodeTrait,
+        S: Clone,
+    {
+        let mut labels =
+            IndexMap::with_capacity_and_hasher(self.node_count(), self.nodes.hasher().clone());
+        let mut gr = GraphMap::with_capacity_and_hasher(
+            self.node_count(),
+            self.edge_count(),
+            self.nodes.hasher().clone(),
+        );
+        for &n in self.nodes.keys() {
+            let label = node_map(n);
+            if gr.contains_node(label) {
+                panic!("GraphMap: two nodes map to the same label");
+            }
+            labels.insert(n, gr.add_node(label));
+        }
+        for (&(a, b), weight) in &self.edges {
+            gr.add_edge(labels[&a], labels[&b], edge_map(a, b, weight));
+        }
+        gr
+    }
+
+    /// Create a new `GraphMap` with the edges for which `predicate` returns
+    /// `true`, cloning their weights.
+    ///
+    /// All nodes are kept, even if they lose all their edges.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let g = UnGraphMap::<_, i32>::from_edges(&[(0, 1, -1), (1, 2, 2)]);
+    /// let h = g.filter_edges(|_, _, &w| w > 0);
+    /// assert_eq!(h.node_count(), 3);
+    /// assert_eq!(h.edge_count(), 1);
+    /// ```
+    pub fn filter_edges<F>(&self, mut predicate: F) -> Self
+    where
+        F: FnMut(N, N, &E) -> bool,
+        E: Clone,
+        S: Clone,
+    {
+        self.filter_map(Some, |a, b, weight| {
+            if predicate(a, b, weight) {
+                Some(weight.clone())
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Create a new `GraphMap` with every node relabeled by `f`.
+    ///
+    /// Edges, including self loops, keep their weights and directions with
+    /// their endpoints mapped through `f`. `f` is called once per node.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// **Panics** if `f` maps two distinct nodes to the same label.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<u32, ()>::from_edges(&[(10, 20), (20, 30), (30, 30)]);
+    /// let h = g.relabel_nodes(|n| (n / 10 - 1) as u8);
+    /// assert!(h.contains_edge(0, 1) && h.contains_edge(1, 2) && h.contains_edge(2, 2));
+    /// assert_eq!(h.edge_count(), 3);
+    /// ```
+    pub fn relabel_nodes<M, F>(&self, f: F) -> GraphMap<M, E, Ty, S>
+    where
+        M: NodeTrait,
+        F: FnMut(N) -> M,
+        E: Clone,
+        S: Clone,
+    {
+        self.map(f, |_, _, weight| weight.clone())
+    }
+
+    /// Create a new `GraphMap` by mapping nodes and edges, and excluding
+    /// those mapped to `None`.
+    ///
+    /// `node_map` is called once for each node. A node is excluded from the
+    /// resulting graph, together with its edges, if it is mapped to `None`;
+    /// otherwise it is rekeyed to the returned identifier, like in
+    /// [`.map()`](#method.map). Then `edge_map` is called with the original
+    /// endpoints and weight of each edge that has not had any endpoint
+    /// removed, and the edge is excluded if it is mapped to `None`.
+    ///
+    /// The resulting graph has the structure of a subgraph of the original
+    /// graph; excluding an edge never removes its endpoints.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// **Panics** if `node_map` maps two distinct nodes to the same
+    /// identifier.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let g = UnGraphMap::<_, i32>::from_edges(&[(0, 1, -1), (1, 2, 2), (2, 3, 3)]);
+    /// let h = g.filter_map(
+    ///     |n| if n != 3 { Some(n) } else { None },
+    ///     |_, _, &w| if w > 0 { Some(w as u32) } else { None },
+    /// );
+    /// assert_eq!(h.node_count(), 3);
+    /// assert_eq!(h.edge_count(), 1);
+    /// assert_eq!(h[(1, 2)], 2);
+    /// ```
+    pub fn filter_map<F, G, N2, E2>(
+        &self,
+        mut node_map: F,
+        mut edge_map: G,
+    ) -> GraphMap<N2, E2, Ty, S>
+    where
+        F: FnMut(N) -> Option<N2>,
+        G: FnMut(N, N, &E) -> Option<E2>,
+        N2: NodeTrait,
+        S: Clone,
+    {
+        let mut labels = IndexMap::with_hasher(self.nodes.hasher().clone());
+        let mut gr = GraphMap::with_hasher(self.nodes.hasher().clone());
+        for &n in self.nodes.keys() {
+            if let Some(label) = node_map(n) {
+                if gr.contains_node(label) {
+                    panic!("GraphMap::filter_map: two nodes map to the same label");
+                }
+                labels.insert(n, gr.add_node(label));
+            }
+        }
+        for (&(a, b), weight) in &self.edges {
+            if let (Some(&la), Some(&lb)) = (labels.get(&a), labels.get(&b)) {
+                if let Some(weight) = edge_map(a, b, weight) {
+                    gr.add_edge(la, lb, weight);
+                }
+            }
+        }
+        gr
+    }
+
+    /// Create the subgraph induced by `nodes`.
+    ///
+    /// The resulting graph contains the listed nodes that exist in `self`,
+    /// and every edge of `self` whose endpoints are both among them, with its
+    /// weight cloned. Nodes that are not in `self` are ignored.
+    ///
+    /// Computes in **O(|V'| + |E'|)** time (average), where `V'` are the
+    /// listed nodes and `E'` their edges in `self`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3)]);
+    /// let h = g.subgraph(vec![0, 2, 3, 7]);
+    /// assert_eq!(h.node_count(), 3);
+    /// assert!(h.contains_edge(2, 0) && h.contains_edge(2, 3));
+    /// assert_eq!(h.edge_count(), 2);
+    /// ```
+    pub fn subgraph<I>(&self, nodes: I) -> Self
+    where
+        I: IntoIterator<Item = N>,
+        E: Clone,
+        S: Clone,
+    {
+        let mut gr = GraphMap::with_hasher(self.nodes.hasher().clone());
+        for n in nodes {
+            if self.contains_node(n) {
+                gr.add_node(n);
+            }
+        }
+        for i in 0..gr.nodes.len() {
+            let (&a, _) = gr.nodes.get_index(i).unwrap();
+            for &(b, dir) in &self.nodes[&a] {
+                // every edge has exactly one outgoing adjacency entry
+                if dir == CompactDirection::Outgoing && gr.contains_node(b) {
+                    gr.add_edge(a, b, self.edges[&Self::edge_key(a, b)].clone());
+                }
+            }
+        }
+        gr
+    }
+
+    /// Return `true` if every node of the graph is a node of `other`, and
+    /// every edge of the graph is an edge of `other` with an equal weight.
+    ///
+    /// The iteration order of the graphs doesn't matter.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2)]);
+    /// let mut h = g.clone();
+    /// h.remove_edge(1, 0);
+    /// assert!(h.is_subgraph_of(&g));
+    /// assert!(!g.is_subgraph_of(&h));
+    /// ```
+    pub fn is_subgraph_of(&self, other: &Self) -> bool
+    where
+        E: PartialEq,
+    {
+        self.node_count() <= other.node_count()
+            && self.edge_count() <= other.edge_count()
+            && self.nodes.keys().all(|n| other.nodes.contains_key(n))
+            && self
+                .edges
+                .iter()
+                .all(|(edge, weight)| other.edges.get(edge) == Some(weight))
+    }
+
+    /// Return an iterator over the edges of the graph that are not edges of
+    /// `other`, ignoring their weights.
+    ///
+    /// Iterator element type is `(N, N, &E)`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2)]);
+    /// let h = DiGraphMap::<_, ()>::from_edges(&[(1, 2), (2, 0)]);
+    /// assert_eq!(g.edge_difference(&h).collect::<Vec<_>>(), vec![(0, 1, &())]);
+    /// ```
+    pub fn edge_difference<'a>(
+        &'a self,
+        other: &'a Self,
+    ) -> impl Iterator<Item = (N, N, &'a E)> + 'a {
+        self.edges
+            .iter()
+            .filter(move |&(edge, _)| !other.edges.contains_key(edge))
+            .map(|(&(a, b), weight)| (a, b, weight))
+    }
+
+    /// Return an iterator over the edges that are in only one of the graph
+    /// and `other`, ignoring their weights: first those of the graph, then
+    /// those of `other`.
+    ///
+    /// Iterator element type is `(N, N, &E)`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2)]);
+    /// let h = DiGraphMap::<_, ()>::from_edges(&[(1, 2), (2, 0)]);
+    /// let diff = g.symmetric_edge_difference(&h).map(|(a, b, _)| (a, b));
+    /// assert_eq!(diff.collect::<Vec<_>>(), vec![(0, 1), (2, 0)]);
+    /// ```
+    pub fn symmetric_edge_difference<'a>(
+        &'a self,
+        other: &'a Self,
+    ) -> impl Iterator<Item = (N, N, &'a E)> + 'a {
+        self.edge_difference(other)
+            .chain(other.edge_difference(self))
+    }
+
+    /// Create the complement of the graph.
+    ///
+    /// The resulting graph has the same nodes, and an edge with weight
+    /// `default_weight` between every pair of distinct nodes that are not
+    /// connected in `self`. Edges of `self` are omitted. Self loops are never
+    /// added, whether or not `self` has any.
+    ///
+    /// For a directed graph each ordered pair of nodes is considered, for an
+    /// undirected graph each unordered pair is considered once.
+    ///
+    /// Computes in **O(|V|^2)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2)]);
+    /// let c = g.complement(());
+    /// assert_eq!(c.edge_count(), 1);
+    /// assert!(c.contains_edge(2, 0));
+    /// ```
+    pub fn complement(&self, default_weight: E) -> Self
+    where
+        E: Clone,
+        S: Clone,
+    {
+        let mut gr =
+            GraphMap::with_capacity_and_hasher(self.node_count(), 0, self.nodes.hasher().clone());
+        for &n in self.nodes.keys() {
+            gr.add_node(n);
+        }
+        for (i, &a) in self.nodes.keys().enumerate() {
+            let start = if Ty::is_directed() { 0 } else { i + 1 };
+            for &b in self.nodes.keys().skip(start) {
+                if a != b && !self.contains_edge(a, b) {
+                    gr.add_edge(a, b, default_weight.clone());
+                }
+            }
+        }
+        gr
+    }
+
+    /// Extend the graph from an iterable of edges, like `Extend`, but call
+    /// `merge` with the current and the new weight when an edge already
+    /// exists instead of overwriting it.
+    ///
+    /// Nodes are inserted automatically to match the edges.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let mut g = UnGraphMap::<_, u32>::new();
+    /// g.extend_with(&[(0, 1, 1), (1, 2, 1), (1, 0, 1)], |w, new| *w += new);
+    /// assert_eq!(g[(0, 1)], 2);
+    /// ```
+    pub fn extend_with<I, F>(&mut self, iterable: I, mut merge: F)
+    where
+        I: IntoIterator,
+        I::Item: IntoWeightedEdge<E, NodeId = N>,
+        F: FnMut(&mut E, E),
+    {
+        let iter = iterable.into_iter();
+        let (low, _) = iter.size_hint();
+        self.edges.reserve(low);
+
+        for elt in iter {
+            let (source, target, weight) = elt.into_weighted_edge();
+            match self.edges.get_mut(&Self::edge_key(source, target)) {
+                Some(old) => merge(old, weight),
+                None => {
+                    self.add_edge(source, target, weight);
+                }
+            }
+        }
+    }
+
+    /// Return the adjacency matrix of the graph, together with the node
+    /// order used for its rows and columns.
+    ///
+    /// The node order is the iteration order of `.nodes()`. The matrix is
+    /// stored in row-major order with `n * n` entries for `n` nodes: entry
+    /// `i * n + j` is the weight of the edge from node `i` to node `j`, or
+    /// `E::default()` if there is no such edge. The matrix of an undirected
+    /// graph is symmetric.
+    ///
+    /// Computes in **O(|V|^2)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<_, f64>::from_edges(&[('a', 'b', 0.5), ('b', 'b', 2.)]);
+    /// let (nodes, matrix) = g.to_adjacency_matrix();
+    /// assert_eq!(nodes, vec!['a', 'b']);
+    /// assert_eq!(matrix, vec![0., 0.5, 0., 2.]);
+    /// ```
+    pub fn to_adjacency_matrix(&self) -> (Vec<N>, Vec<E>)
+    where
+        E: Clone + Default,
+    {
+        let n = self.node_count();
+        let mut matrix = vec![E::default(); n * n];
+        for (&(a, b), weight) in &self.edges {
+            let i = self.nodes.get_index_of(&a).unwrap();
+            let j = self.nodes.get_index_of(&b).unwrap();
+            matrix[i * n + j] = weight.clone();
+            if !Ty::is_directed() {
+                matrix[j * n + i] = weight.clone();
+            }
+        }
+        (self.nodes.keys().cloned().collect(), matrix)
+    }
+
+    /// Return the graph in graphviz .dot format, with each node identified by
+    /// its `Display` representation, and edges labeled with their weights.
+    ///
+    /// Use [`Dot`](../dot/struct.Dot.html) for more options.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<_, u32>::from_edges(&[("a", "b", 1)]);
+    /// let dot = g.to_dot();
+    /// assert!(dot.contains(r#""a" -> "b" [ label = "1" ]"#));
+    ///
+    /// // The output looks like this:
+    /// //
+    /// // digraph {
+    /// //     "a"
+    /// //     "b"
+    /// //     "a" -> "b" [ label = "1" ]
+    /// // }
+    /// ```
+    pub fn to_dot(&self) -> String
+    where
+        N: fmt::Display,
+        E: fmt::Display,
+    {
+        use std::fmt::Write;
+
+        let (kind, edge) = if Ty::is_directed() {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        let mut dot = String::new();
+        // writing to a String can't fail
+        writeln!(dot, "{} {{", kind).unwrap();
+        for n in self.nodes.keys() {
+            writeln!(dot, "    \"{}\"", Escaped(n)).unwrap();
+        }
+        for ((a, b), weight) in &self.edges {
+            writeln!(
+                dot,
+                "    \"{}\" {} \"{}\" [ label = \"{}\" ]",
+                Escaped(a),
+                edge,
+                Escaped(b),
+                Escaped(weight)
+            )
+            .unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Create the line graph of the graph.
+    ///
+    /// Every edge of `self` becomes a node of the line graph, identified by
+    /// its endpoints in the order of `.all_edges()`. Two of these nodes are
+    /// connected if their edges share an endpoint, whatever the edge
+    /// directions.
+    ///
+    /// Computes in **O(|E| + Σ deg(v)²)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (1, 3)]);
+    /// let l = g.line_graph();
+    /// assert_eq!(l.node_count(), 3);
+    /// assert_eq!(l.edge_count(), 3);
+    /// assert!(l.contains_edge((0, 1), (1, 3)));
+    /// ```
+    pub fn line_graph(&self) -> GraphMap<(N, N), (), Undirected, S>
+    where
+        S: Clone,
+    {
+        let mut gr =
+            GraphMap::with_capacity_and_hasher(self.edge_count(), 0, self.nodes.hasher().clone());
+        for &edge in self.edges.keys() {
+            gr.add_node(edge);
+        }
+        let mut incident = Vec::new();
+        for (&n, neighbors) in &self.nodes {
+            incident.clear();
+            incident.extend(neighbors.iter().map(|&(m, dir)| match dir {
+                CompactDirection::Outgoing => Self::edge_key(n, m),
+                CompactDirection::Incoming => Self::edge_key(m, n),
+            }));
+            for (i, &e) in incident.iter().enumerate() {
+                for &f in &incident[i + 1..] {
+                    gr.add_edge(e, f, ());
+                }
+            }
+        }
+        gr
+    }
+
+    /// Add all nodes and edges of `other` to `self`.
+    ///
+    /// When an edge exists in both graphs, `merge` is called with the weight
+    /// in `self` and a clone of the weight in `other`; pass
+    /// `|w, new| *w = new` to let `other` overwrite.
+    ///
+    /// Computes in **O(|V'| + |E'|)** time (average), where `V'` and `E'` are
+    /// the nodes and edges of `other`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let mut g = UnGraphMap::<_, u32>::from_edges(&[(0, 1, 1), (1, 2, 1)]);
+    /// let h = UnGraphMap::<_, u32>::from_edges(&[(2, 1, 1), (2, 3, 1)]);
+    /// g.extend_from(&h, |w, new| *w += new);
+    /// assert_eq!(g.edge_count(), 3);
+    /// assert_eq!(g[(1, 2)], 2);
+    /// ```
+    pub fn extend_from<F>(&mut self, other: &Self, mut merge: F)
+    where
+        E: Clone,
+        F: FnMut(&mut E, E),
+    {
+        self.nodes.reserve(other.nodes.len());
+        for &n in other.nodes.keys() {
+            self.add_node(n);
+        }
+        for (&(a, b), weight) in &other.edges {
+            match self.edges.get_mut(&(a, b)) {
+                Some(old) => merge(old, weight.clone()),
+                None => {
+                    self.add_edge(a, b, weight.clone());
+                }
+            }
+        }
+    }
+
+    /// Return the union of the graph and `other`: a graph with the nodes and
+    /// edges of both, like `extend_from` on a clone of `self`.
+    ///
+    /// When an edge exists in both graphs, `merge` is called with the weight
+    /// taken from `self` and a clone of the weight in `other`.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average), for the nodes and edges
+    /// of both graphs.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<_, u32>::from_edges(&[(0, 1, 1), (1, 2, 2)]);
+    /// let h = DiGraphMap::<_, u32>::from_edges(&[(1, 2, 5), (3, 0, 1)]);
+    /// let u = g.union(&h, |w, new| *w = (*w).max(new));
+    /// assert_eq!(u.node_count(), 4);
+    /// assert_eq!(u.edge_count(), 3);
+    /// assert_eq!(u[(1, 2)], 5);
+    /// ```
+    pub fn union<F>(&self, other: &Self, merge: F) -> Self
+    where
+        E: Clone,
+        S: Clone,
+        F: FnMut(&mut E, E),
+    {
+        let mut gr = self.clone();
+        gr.extend_from(other, merge);
+        gr
+    }
+
+    /// Return the intersection of the graph and `other`: a graph with the
+    /// nodes and edges that are in both, with the weights combined by
+    /// `merge`, which is passed the weight in `self` first.
+    ///
+    /// Nodes and edges keep their relative iteration order in `self`.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let g = UnGraphMap::<_, u32>::from_edges(&[(0, 1, 1), (1, 2, 2)]);
+    /// let h = UnGraphMap::<_, u32>::from_edges(&[(2, 1, 5), (2, 3, 1)]);
+    /// let i = g.intersection(&h, |a, b| a + b);
+    /// assert_eq!(i.nodes().collect::<Vec<_>>(), vec![1, 2]);
+    /// assert_eq!(i.all_edges().collect::<Vec<_>>(), vec![(1, 2, &7)]);
+    /// ```
+    pub fn intersection<F>(&self, other: &Self, mut merge: F) -> Self
+    where
+        S: Clone,
+        F: FnMut(&E, &E) -> E,
+    {
+        let mut gr = GraphMap::with_hasher(self.nodes.hasher().clone());
+        for &n in self.nodes.keys() {
+            if other.nodes.contains_key(&n) {
+                gr.add_node(n);
+            }
+        }
+        for (&(a, b), weight) in &self.edges {
+            if let Some(other_weight) = other.edges.get(&(a, b)) {
+                gr.add_edge(a, b, merge(weight, other_weight));
+            }
+        }
+        gr
+    }
+
+    /// Return the difference of the graph and `other`: a graph with the same
+    /// nodes as `self`, and the edges of `self` that are not in `other`.
+    ///
+    /// Edge weights are ignored when comparing edges. Use `edge_difference`
+    /// to only iterate over the edges.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    /// let h = DiGraphMap::<_, ()>::from_edges(&[(1, 2), (0, 2)]);
+    /// let d = g.difference(&h);
+    /// assert_eq!(d.node_count(), 3);
+    /// assert_eq!(d.all_edges().collect::<Vec<_>>(), vec![(0, 1, &()), (2, 0, &())]);
+    /// ```
+    pub fn difference(&self, other: &Self) -> Self
+    where
+        E: Clone,
+        S: Clone,
+    {
+        let mut gr = GraphMap::with_hasher(self.nodes.hasher().clone());
+        for &n in self.nodes.keys() {
+            gr.add_node(n);
+        }
+        for (a, b, weight) in self.edge_difference(other) {
+            gr.add_edge(a, b, weight.clone());
+        }
+        gr
+    }
+
+    /// Return a `Graph` that corresponds to this `GraphMap`.
+    ///
+    /// 1. Note that node and edge indices in the `Graph` have nothing in common
+    ///    with the `GraphMap`s node weights `N`. The node weights `N` are used as
+    ///    node weights in the resulting `Graph`, too.
+    /// 2. Note that the index type is user-chosen.
+    ///
+    /// The node with index `i` in the `Graph` is the `i`th node in the
+    /// iteration order of `.nodes()`. Use `into_graph_with_map` or `to_graph`
+    /// to also get a map from each node to its index.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// **Panics** if the number of nodes or edges does not fit with
+    /// the resulting graph's index type.
+    pub fn into_graph<Ix>(self) -> Graph<N, E, Ty, Ix>
+    where
+        Ix: IndexType,
+    {
+        self.into_graph_with_hasher(RandomState::new()).0
+    }
+
+    /// Return a `Graph` that corresponds to this `GraphMap`, together with
+    /// the mapping from each node `N` to its node index in the `Graph`.
+    ///
+    /// The node with index `i` in the `Graph` is the `i`th node in the
+    /// iteration order of `.nodes()`, and so is its entry in the map. The
+    /// index type is user-chosen.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// **Panics** if the number of nodes or edges does not fit with
+    /// the resulting graph's index type.
+    ///
+    /// ```
+    /// use petgraph::algo::toposort;
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let gm = DiGraphMap::<_, ()>::from_edges(&[("b", "c"), ("a", "b")]);
+    /// let (graph, indices) = gm.into_graph_with_map::<u32>();
+    /// let order = toposort(&graph, None).unwrap();
+    /// assert_eq!(order, vec![indices["a"], indices["b"], indices["c"]]);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn into_graph_with_map<Ix>(self) -> (Graph<N, E, Ty, Ix>, NodeIndexMap<N, Ix, S>)
+    where
+        Ix: IndexType,
+        S: Clone,
+    {
+        let hasher = self.nodes.hasher().clone();
+        self.into_graph_with_hasher(hasher)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn into_graph_with_hasher<Ix, H>(
+        self,
+        hasher: H,
+    ) -> (Graph<N, E, Ty, Ix>, NodeIndexMap<N, Ix, H>)
+    where
+        Ix: IndexType,
+        H: BuildHasher,
+    {
+        let (mut gr, indices) = self.graph_nodes(hasher);
+        for ((a, b), edge_weight) in self.edges {
+            gr.add_edge(indices[&a], indices[&b], edge_weight);
+        }
+        (gr, indices)
+    }
+
+    /// Return a `Graph` with the nodes of `self` in iteration order and no
+    /// edges, together with the mapping from each node to its node index.
+    #[allow(clippy::type_complexity)]
+    fn graph_nodes<Ix, H>(&self, hasher: H) -> (Graph<N, E, Ty, Ix>, NodeIndexMap<N, Ix, H>)
+    where
+        Ix: IndexType,
+        H: BuildHasher,
+    {
+        let mut gr = Graph::with_capacity(self.node_count(), self.edge_count());
+        let mut indices = IndexMap::with_capacity_and_hasher(self.node_count(), hasher);
+        for &node in self.nodes.keys() {
+            indices.insert(node, gr.add_node(node));
+        }
+        (gr, indices)
+    }
+
+    /// Consume the graph and return its nodes and edges.
+    ///
+    /// The nodes, including isolated nodes, are in the iteration order of
+    /// `.nodes()`, and the edges are `(source, target, weight)` triples in the
+    /// iteration order of `.all_edges()`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let mut g = UnGraphMap::<_, i32>::from_edges(&[(1, 2, 12)]);
+    /// g.add_node(3);
+    /// let (nodes, edges) = g.into_nodes_edges();
+    /// assert_eq!(nodes, vec![1, 2, 3]);
+    /// assert_eq!(edges, vec![(1, 2, 12)]);
+    /// ```
+    pub fn into_nodes_edges(self) -> (Vec<N>, Vec<(N, N, E)>) {
+        let nodes = self.nodes.into_iter().map(|(n, _)| n).collect();
+        let edges = self
+            .edges
+            .into_iter()
+            .map(|((a, b), weight)| (a, b, weight))
+            .collect();
+        (nodes, edges)
+    }
+
+    /// Return a consuming iterator of the nodes of the graph, in the
+    /// iteration order of `.nodes()`.
+    ///
+    /// Iterator element type is `N`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<_, ()>::from_edges(&[("a", "b"), ("c", "a")]);
+    /// assert_eq!(g.into_nodes().collect::<Vec<_>>(), vec!["a", "b", "c"]);
+    /// ```
+    pub fn into_nodes(self) -> IntoNodes<N> {
+        IntoNodes {
+            iter: self.nodes.into_iter(),
+        }
+    }
+
+    /// Return a consuming iterator of the edges of the graph, in the
+    /// iteration order of `.all_edges()`, taking ownership of the weights.
+    ///
+    /// Iterator element type is `(N, N, E)`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let g = UnGraphMap::from_edges(&[(2, 1, vec![1.5]), (2, 3, vec![])]);
+    /// let edges = g.into_edges().collect::<Vec<_>>();
+    /// assert_eq!(edges, vec![(1, 2, vec![1.5]), (2, 3, vec![])]);
+    /// ```
+    pub fn into_edges(self) -> IntoAllEdges<N, E> {
+        IntoAllEdges {
+            iter: self.edges.into_iter(),
+        }
+    }
+
+    /// Return a `Graph` that corresponds to this `GraphMap`, without
+    /// consuming it, together with the mapping from each node `N` to its
+    /// node index in the `Graph`.
+    ///
+    /// Node and edge weights are cloned into the new graph. The index type is
+    /// user-chosen.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// **Panics** if the number of nodes or edges does not fit with
+    /// the resulting graph's index type.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let gm = DiGraphMap::<_, ()>::from_edges(&[("a", "b"), ("b", "c")]);
+    /// let (graph, indices) = gm.to_graph::<u32>();
+    /// assert_eq!(graph[indices["b"]], "b");
+    /// assert!(graph.contains_edge(indices["b"], indices["c"]));
+    /// // the original graph is still available
+    /// assert!(gm.contains_edge("a", "b"));
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn to_graph<Ix>(&self) -> (Graph<N, E, Ty, Ix>, NodeIndexMap<N, Ix, S>)
+    where
+        Ix: IndexType,
+        E: Clone,
+        S: Clone,
+    {
+        let (mut gr, indices) = self.graph_nodes(self.nodes.hasher().clone());
+        for (&(a, b), edge_weight) in &self.edges {
+            gr.add_edge(indices[&a], indices[&b], edge_weight.clone());
+        }
+        (gr, indices)
+    }
+
+    /// Return a view of the graph that identifies nodes by `NodeIndex`, for
+    /// use with index-based algorithms without copying it into a `Graph`.
+    ///
+    /// The node with index `i` is the `i`th node in the iteration order of
+    /// `.nodes()`. Edge weights are borrowed, not cloned.
+    ///
+    /// ```
+    /// use petgraph::algo::has_path_connecting;
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let gm = DiGraphMap::<_, Vec<u8>>::from_edges(&[
+    ///     ("a", "b", vec![1]),
+    ///     ("b", "c", vec![2]),
+    /// ]);
+    /// let view = gm.graph_view::<u32>();
+    /// let a = view.node_index("a").unwrap();
+    /// let c = view.node_index("c").unwrap();
+    /// assert!(has_path_connecting(view, a, c, None));
+    /// ```
+    pub fn graph_view<Ix>(&self) -> GraphView<'_, N, E, Ty, Ix, S>
+    where
+        Ix: IndexType,
+    {
+        GraphView::new(self)
+    }
+
+    /// Create a `GraphMap` from a `Graph`, using its node weights as node
+    /// identifiers.
+    ///
+    /// This is the inverse of `into_graph` for graphs with unique node weights
+    /// and no parallel edges. Parallel edges are collapsed into one, keeping
+    /// the weight of the edge with the highest index.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// **Panics** if two nodes of `graph` have the same weight.
+    ///
+    /// ```
+    /// use petgraph::graph::DiGraph;
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let mut graph = DiGraph::<_, _>::new();
+    /// let a = graph.add_node("a");
+    /// let b = graph.add_node("b");
+    /// graph.add_edge(a, b, 1.5);
+    ///
+    /// let gm = DiGraphMap::from_graph(graph);
+    /// assert_eq!(gm.edge_weight("a", "b"), Some(&1.5));
+    /// ```
+    pub fn from_graph<Ix>(graph: Graph<N, E, Ty, Ix>) -> Self
+    where
+        Ix: IndexType,
+        S: Default,
+    {
+        let mut new_graph = GraphMap::with_capacity(graph.node_count(), graph.edge_count());
+        let (nodes, edges) = graph.into_nodes_edges();
+        for node in &nodes {
+            assert!(
+                !new_graph.contains_node(node.weight),
+                "GraphMap::from_graph: duplicate node weight"
+            );
+            new_graph.add_node(node.weight);
+        }
+        for edge in edges {
+            let a = nodes[edge.source().index()].weight;
+            let b = nodes[edge.target().index()].weight;
+            new_graph.add_edge(a, b, edge.weight);
+        }
+        new_graph
+    }
+}
+
+impl<N, E, S> GraphMap<N, E, Directed, S>
+where
+    N: NodeTrait,
+    S: BuildHasher,
+{
+    /// Return an iterator of all nodes with an edge starting from or ending
+    /// at `a`, ignoring the direction of the edges.
+    ///
+    /// A node connected to `a` in both directions is produced once, and so is
+    /// `a` itself if it has a self loop.
+    ///
+    /// Produces an empty iterator if the node doesn't exist.<br>
+    /// Iterator element type is `N`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 0), (2, 0)]);
+    /// assert_eq!(g.neighbors_undirected(0).collect::<Vec<_>>(), vec![1, 2]);
+    /// ```
+    pub fn neighbors_undirected(&self, a: N) -> impl Iterator<Item = N> + '_ {
+        let links = match self.nodes.get(&a) {
+            Some(neigh) => &neigh[..],
+            None => &[],
+        };
+        links.iter().filter_map(move |&(n, dir)| {
+            // an incoming edge is skipped if the outgoing one is listed too
+            if dir == Incoming && self.edges.contains_key(&(a, n)) {
+                None
+            } else {
+                Some(n)
+            }
+        })
+    }
+
+    /// Convert the graph into an undirected `GraphMap` with the same nodes,
+    /// in the same order.
+    ///
+    /// Each edge `a -> b` becomes an undirected edge between `a` and `b`. If
+    /// the graph has edges in both directions, the weight of the one that
+    /// comes last in the iteration order of `.all_edges()` is kept; use
+    /// `into_undirected_with` to combine them instead.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::from_edges(&[(1, 2, 'a'), (2, 1, 'b')]);
+    /// let ug = g.into_undirected();
+    /// assert_eq!(ug.edge_count(), 1);
+    /// assert_eq!(ug[(1, 2)], 'b');
+    /// ```
+    pub fn into_undirected(self) -> GraphMap<N, E, Undirected, S>
+    where
+        S: Clone,
+    {
+        self.into_undirected_with(|weight, new| *weight = new)
+    }
+
+    /// Convert the graph into an undirected `GraphMap`, like
+    /// `into_undirected`, calling `merge` with the current and the new weight
+    /// when two edges collapse into one.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::from_edges(&[(1, 2, 3), (2, 1, 4), (2, 3, 1)]);
+    /// let ug = g.into_undirected_with(|w, new| *w += new);
+    /// assert_eq!(ug[(1, 2)], 7);
+    /// assert_eq!(ug[(3, 2)], 1);
+    /// ```
+    pub fn into_undirected_with<F>(self, merge: F) -> GraphMap<N, E, Undirected, S>
+    where
+        F: FnMut(&mut E, E),
+        S: Clone,
+    {
+        let mut gr = GraphMap::with_capacity_and_hasher(
+            self.node_count(),
+            self.edge_count(),
+            self.nodes.hasher().clone(),
+        );
+        for &node in self.nodes.keys() {
+            gr.add_node(node);
+        }
+        gr.extend_with(self.edges.into_iter().map(|((a, b), w)| (a, b, w)), merge);
+        gr
+    }
+}
+
+impl<N, E, S> GraphMap<N, E, Undirected, S>
+where
+    N: NodeTrait,
+    S: BuildHasher,
+{
+    /// Convert the graph into a directed `GraphMap` with the same nodes, in
+    /// the same order.
+    ///
+    /// Each undirected edge between `a` and `b` becomes the two edges
+    /// `a -> b` and `b -> a` with the same weight, and a self loop stays a
+    /// single self loop.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let g = UnGraphMap::from_edges(&[(1, 2, 'a'), (3, 3, 'b')]);
+    /// let dg = g.into_directed();
+    /// assert_eq!(dg.edge_count(), 3);
+    /// assert_eq!(dg[(2, 1)], 'a');
+    /// ```
+    pub fn into_directed(self) -> GraphMap<N, E, Directed, S>
+    where
+        E: Clone,
+        S: Clone,
+    {
+        let mut gr = GraphMap::with_capacity_and_hasher(
+            self.node_count(),
+            2 * self.edge_count(),
+            self.nodes.hasher().clone(),
+        );
+        for &node in self.nodes.keys() {
+            gr.add_node(node);
+        }
+        for ((a, b), weight) in self.edges {
+            if a != b {
+                gr.add_edge(b, a, weight.clone());
+            }
+            gr.add_edge(a, b, weight);
+        }
+        gr
+    }
+}
+
+/// Create a new `GraphMap` from an iterable of edges.
+impl<N, E, Ty, S, Item> FromIterator<Item> for GraphMap<N, E, Ty, S>
+where
+    Item: IntoWeightedEdge<E, NodeId = N>,
+    N: NodeTrait,
+    Ty: EdgeType,
+    S: BuildHasher + Default,
+{
+    fn from_iter<I>(iterable: I) -> Self
+    where
+        I: IntoIterator<Item = Item>,
+    {
+        let iter = iterable.into_iter();
+        let (low, _) = iter.size_hint();
+        // a connected graph has at most one node more than it has edges
+        let mut g = Self::with_capacity(low, low);
+        g.extend(iter);
+        g
+    }
+}
+
+/// Extend the graph from an iterable of edges.
+///
+/// Nodes are inserted automatically to match the edges.
+impl<N, E, Ty, S, Item> Extend<Item> for GraphMap<N, E, Ty, S>
+where
+    Item: IntoWeightedEdge<E, NodeId = N>,
+    N: NodeTrait,
+    Ty: EdgeType,
+    S: BuildHasher,
+{
+    fn extend<I>(&mut self, iterable: I)
+    where
+        I: IntoIterator<Item = Item>,
+    {
+        let iter = iterable.into_iter();
+        let (low, _) = iter.size_hint();
+        self.edges.reserve(low);
+
+        for elt in iter {
+            let (source, target, weight) = elt.into_weighted_edge();
+            self.add_edge(source, target, weight);
+        }
+    }
+}
+
+iterator_wrap! {
+    impl (Iterator DoubleEndedIterator ExactSizeIterator) for
+    struct Nodes <'a, N> where { N: 'a + NodeTrait }
+    item: N,
+    iter: Cloned<Keys<'a, N, Vec<(N, CompactDirection)>>>,
+}
+
+pub struct IntoNodes<N> {
+    iter: IndexMapIntoIter<N, Vec<(N, CompactDirection)>>,
+}
+
+impl<N> Iterator for IntoNodes<N> {
+    type Item = N;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(n, _)| n)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<N> DoubleEndedIterator for IntoNodes<N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(n, _)| n)
+    }
+}
+
+impl<N> ExactSizeIterator for IntoNodes<N> {}
+
+pub struct IntoAllEdges<N, E> {
+    iter: IndexMapIntoIter<(N, N), E>,
+}
+
+impl<N, E> Iterator for IntoAllEdges<N, E> {
+    type Item = (N, N, E);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|((a, b), weight)| (a, b, weight))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<N, E> DoubleEndedIterator for IntoAllEdges<N, E> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|((a, b), weight)| (a, b, weight))
+    }
+}
+
+impl<N, E> ExactSizeIterator for IntoAllEdges<N, E> {}
+
+pub struct Neighbors<'a, N, Ty = Undirected>
+where
+    N: 'a,
+    Ty: EdgeType,
+{
+    iter: Iter<'a, (N, CompactDirection)>,
+    ty: PhantomData<Ty>,
+}
+
+impl<'a, N, Ty> Iterator for Neighbors<'a, N, Ty>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+{
+    type Item = N;
+    fn next(&mut self) -> Option<N> {
+        if Ty::is_directed() {
+            (&mut self.iter)
+                .filter_map(|&(n, dir)| if dir == Outgoing { Some(n) } else { None })
+                .next()
+        } else {
+            self.iter.next().map(|&(n, _)| n)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        if Ty::is_directed() {
+            (0, upper)
+        } else {
+            (lower, upper)
+        }
+    }
+}
+
+/// For undirected graphs, every adjacency entry is a neighbor, so the
+/// exact length is known.
+impl<'a, N> ExactSizeIterator for Neighbors<'a, N, Undirected> where N: NodeTrait {}
+
+impl<'a, N> DoubleEndedIterator for Neighbors<'a, N, Undirected>
+where
+    N: NodeTrait,
+{
+    fn next_back(&mut self) -> Option<N> {
+        self.iter.next_back().map(|&(n, _)| n)
+    }
+}
+
+pub struct Adjacencies<'a, N>
+where
+    N: 'a,
+{
+    iter: Iter<'a, (N, CompactDirection)>,
+}
+
+impl<'a, N> Iterator for Adjacencies<'a, N>
+where
+    N: NodeTrait,
+{
+    type Item = (N, Direction);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|&(n, dir)| (n, dir.direction()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, N> ExactSizeIterator for Adjacencies<'a, N> where N: NodeTrait {}
+
+impl<'a, N> DoubleEndedIterator for Adjacencies<'a, N>
+where
+    N: NodeTrait,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|&(n, dir)| (n, dir.direction()))
+    }
+}
+
+pub struct NodeAdjacencies<'a, N>
+where
+    N: 'a,
+{
+    iter: IndexMapIter<'a, N, Vec<(N, CompactDirection)>>,
+}
+
+impl<'a, N> Iterator for NodeAdjacencies<'a, N>
+where
+    N: NodeTrait,
+{
+    type Item = (N, Adjacencies<'a, N>);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|(&n, neigh)| (n, Adjacencies { iter: neigh.iter() }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, N> ExactSizeIterator for NodeAdjacencies<'a, N> where N: NodeTrait {}
+
+impl<'a, N> DoubleEndedIterator for NodeAdjacencies<'a, N>
+where
+    N: NodeTrait,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next_back()
+            .map(|(&n, neigh)| (n, Adjacencies { iter: neigh.iter() }))
+    }
+}
+
+pub struct NeighborsDirected<'a, N, Ty>
+where
+    N: 'a,
+    Ty: EdgeType,
+{
+    iter: Iter<'a, (N, CompactDirection)>,
+    start_node: N,
+    dir: Direction,
+    ty: PhantomData<Ty>,
+}
+
+impl<'a, N, Ty> Iterator for NeighborsDirected<'a, N, Ty>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+{
+    type Item = N;
+    fn next(&mut self) -> Option<N> {
+        if Ty::is_directed() {
+            let self_dir = self.dir;
+            let start_node = self.start_node;
+            (&mut self.iter)
+                .filter_map(move |&(n, dir)| {
+                    if dir == self_dir || n == start_node {
+                        Some(n)
+                    } else {
+                        None
+                    }
+                })
+                .next()
+        } else {
+            self.iter.next().map(|&(n, _)| n)
+        }
+    }
+}
+
+pub struct Edges<'a, N, E: 'a, Ty, S = RandomState>
+where
+    N: 'a + NodeTrait,
+    Ty: EdgeType,
+    S: 'a + BuildHasher,
+{
+    from: N,
+    edges: &'a IndexMap<(N, N), E, S>,
+    iter: Neighbors<'a, N, Ty>,
+}
+
+impl<'a, N, E, Ty, S> Iterator for Edges<'a, N, E, Ty, S>
+where
+    N: 'a + NodeTrait,
+    E: 'a,
+    Ty: EdgeType,
+    S: BuildHasher,
+{
+    type Item = (N, N, &'a E);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|b| {
+            let a = self.from;
+            match self.edges.get(&GraphMap::<N, E, Ty, S>::edge_key(a, b)) {
+                None => unreachable!(),
+                Some(edge) => (a, b, edge),
+            }
+        })
+    }
+}
+
+pub struct EdgesDirected<'a, N, E: 'a, Ty, S = RandomState>
+where
+    N: 'a + NodeTrait,
+    Ty: EdgeType,
+    S: 'a + BuildHasher,
+{
+    from: N,
+    dir: Direction,
+    edges: &'a IndexMap<(N, N), E, S>,
+    iter: Iter<'a, (N, CompactDirection)>,
+    // also produce directed self loops as `Incoming`
+    self_loops: bool,
+    ty: PhantomData<Ty>,
+}
+
+impl<'a, N, E, Ty, S> Iterator for EdgesDirected<'a, N, E, Ty, S>
+where
+    N: 'a + NodeTrait,
+    E: 'a,
+    Ty: EdgeType,
+    S: BuildHasher,
+{
+    type Item = (N, N, &'a E);
+    fn next(&mut self) -> Option<Self::Item> {
+        let (a, b) = loop {
+            let &(n, dir) = self.iter.next()?;
+            if !Ty::is_directed() {
+                break (self.from, n);
+            } else if dir == self.dir {
+                break match self.dir {
+                    Outgoing => (self.from, n),
+                    Incoming => (n, self.from),
+                };
+            } else if self.self_loops && n == self.from {
+                break (n, n);
+            }
+        };
+        match self.edges.get(&GraphMap::<N, E, Ty, S>::edge_key(a, b)) {
+            None => unreachable!(),
+            Some(edge) => Some((a, b, edge)),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.iter.size_hint();
+        if Ty::is_directed() {
+            (0, upper)
+        } else {
+            (upper.unwrap_or(0), upper)
+        }
+    }
+}
+
+impl<'a, N: 'a, E: 'a, Ty, S> IntoEdgeReferences for &'a GraphMap<N, E, Ty, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    S: BuildHasher,
+{
+    type EdgeRef = (N, N, &'a E);
+    type EdgeReferences = AllEdges<'a, N, E, Ty>;
+    fn edge_references(self) -> Self::EdgeReferences {
+        self.all_edges()
+    }
+}
+
+pub struct AllEdges<'a, N, E: 'a, Ty>
+where
+    N: 'a + NodeTrait,
+{
+    inner: IndexMapIter<'a, (N, N), E>,
+    ty: PhantomData<Ty>,
+}
+
+impl<'a, N, E, Ty> Iterator for AllEdges<'a, N, E, Ty>
+where
+    N: 'a + NodeTrait,
+    E: 'a,
+    Ty: EdgeType,
+{
+    type Item = (N, N, &'a E);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(&(a, b), v)| (a, b, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.inner.count()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner
+            .nth(n)
+            .map(|(&(n1, n2), weight)| (n1, n2, weight))
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        self.inner
+            .last()
+            .map(|(&(n1, n2), weight)| (n1, n2, weight))
+    }
+}
+
+impl<'a, N, E, Ty> DoubleEndedIterator for AllEdges<'a, N, E, Ty>
+where
+    N: 'a + NodeTrait,
+    E: 'a,
+    Ty: EdgeType,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next_back()
+            .map(|(&(n1, n2), weight)| (n1, n2, weight))
+    }
+}
+
+pub struct EdgesMut<'a, N, E: 'a, Ty>
+where
+    N: 'a + NodeTrait,
+{
+    from: N,
+    indices: vec::IntoIter<usize>,
+    position: usize,
+    inner: IndexMapIterMut<'a, (N, N), E>,
+    ty: PhantomData<Ty>,
+}
+
+impl<'a, N, E, Ty> Iterator for EdgesMut<'a, N, E, Ty>
+where
+    N: 'a + NodeTrait,
+    E: 'a,
+    Ty: EdgeType,
+{
+    type Item = (N, N, &'a mut E);
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.indices.next()?;
+        let (&(a, b), weight) = self.inner.nth(index - self.position).unwrap();
+        self.position = index + 1;
+        let target = if a == self.from { b } else { a };
+        Some((self.from, target, weight))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
+
+pub struct AllEdgesMut<'a, N, E: 'a, Ty>
+where
+    N: 'a + NodeTrait,
+{
+    inner: IndexMapIterMut<'a, (N, N), E>,
+    ty: PhantomData<Ty>,
+}
+
+impl<'a, N, E, Ty> Iterator for AllEdgesMut<'a, N, E, Ty>
+where
+    N: 'a + NodeTrait,
+    E: 'a,
+    Ty: EdgeType,
+{
+    type Item = (N, N, &'a mut E);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|(&(n1, n2), weight)| (n1, n2, weight))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.inner.count()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner
+            .nth(n)
+            .map(|(&(n1, n2), weight)| (n1, n2, weight))
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        self.inner
+            .last()
+            .map(|(&(n1, n2), weight)| (n1, n2, weight))
+    }
+}
+
+impl<'a, N, E, Ty> DoubleEndedIterator for AllEdgesMut<'a, N, E, Ty>
+where
+    N: 'a + NodeTrait,
+    E: 'a,
+    Ty: EdgeType,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next_back()
+            .map(|(&(n1, n2), weight)| (n1, n2, weight))
+    }
+}
+
+impl<'a, N: 'a, E: 'a, Ty, S> IntoEdges for &'a GraphMap<N, E, Ty, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    S: BuildHasher,
+{
+    type Edges = Edges<'a, N, E, Ty, S>;
+    fn edges(self, a: Self::NodeId) -> Self::Edges {
+        self.edges(a)
+    }
+}
+
+impl<'a, N: 'a, E: 'a, Ty, S> IntoEdgesDirected for &'a GraphMap<N, E, Ty, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    S: BuildHasher,
+{
+    type EdgesDirected = EdgesDirected<'a, N, E, Ty, S>;
+    /// Unlike the inherent `.edges_directed()`, a directed self loop is
+    /// produced for both `Outgoing` and `Incoming`, like for `Graph`.
+    fn edges_directed(self, a: Self::NodeId, dir: Direction) -> Self::EdgesDirected {
+        EdgesDirected {
+            self_loops: true,
+            ..self.edges_directed(a, dir)
+        }
+    }
+}
+
+/// An edge of a `GraphMap` that may or may not exist, created by
+/// `GraphMap::edge_entry`.
+pub struct EdgeEntry<'a, N, E: 'a, Ty, S = RandomState>
+where
+    N: 'a,
+    Ty: 'a,
+    S: 'a,
+{
+    graph: &'a mut GraphMap<N, E, Ty, S>,
+    a: N,
+    b: N,
+}
+
+impl<'a, N, E, Ty, S> EdgeEntry<'a, N, E, Ty, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    S: BuildHasher,
+{
+    /// Return the endpoints of the edge, as passed to `edge_entry`.
+    pub fn endpoints(&self) -> (N, N) {
+        (self.a, self.b)
+    }
+
+    /// Insert the edge with weight `default` if it doesn't exist, inserting
+    /// its endpoints as needed, and return a mutable reference to its weight.
+    pub fn or_insert(self, default: E) -> &'a mut E {
+        self.or_insert_with(|| default)
+    }
+
+    /// Insert the edge with the weight returned by `default` if it doesn't
+    /// exist, inserting its endpoints as needed, and return a mutable
+    /// reference to its weight.
+    pub fn or_insert_with<F>(self, default: F) -> &'a mut E
+    where
+        F: FnOnce() -> E,
+    {
+        let GraphMap { nodes, edges, .. } = self.graph;
+        match edges.entry(GraphMap::<N, E, Ty, S>::edge_key(self.a, self.b)) {
+            Occupied(ent) => ent.into_mut(),
+            Vacant(ent) => {
+                link_nodes(nodes, self.a, self.b);
+                ent.insert(default())
+            }
+        }
+    }
+
+    /// Call `f` with the weight of the edge if it exists.
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut E),
+    {
+        if let Some(weight) = self.graph.edge_weight_mut(self.a, self.b) {
+            f(weight);
+        }
+        self
+    }
+}
+
+/// Index `GraphMap` by node pairs to access edge weights.
+impl<N, E, Ty, S> Index<(N, N)> for GraphMap<N, E, Ty, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    S: BuildHasher,
+{
+    type Output = E;
+    fn index(&self, index: (N, N)) -> &E {
+        let index = Self::edge_key(index.0, index.1);
+        self.edge_weight(index.0, index.1)
+            .expect("GraphMap::index: no such edge")
+    }
+}
+
+/// Index `GraphMap` by node pairs to access edge weights.
+impl<N, E, Ty, S> IndexMut<(N, N)> for GraphMap<N, E, Ty, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    S: BuildHasher,
+{
+    fn index_mut(&mut self, index: (N, N)) -> &mut E {
+        let index = Self::edge_key(index.0, index.1);
+        self.edge_weight_mut(index.0, index.1)
+            .expect("GraphMap::index: no such edge")
+    }
+}
+
+/// Create a new empty `GraphMap`.
+impl<N, E, Ty, S> Default for GraphMap<N, E, Ty, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    S: BuildHasher + Default,
+{
+    fn default() -> Self {
+        GraphMap::with_capacity(0, 0)
+    }
+}
+
+/// Compare two graphs by structure: the same node set, the same edge set and
+/// equal weights for each edge.
+///
+/// Insertion order is ignored, so graphs built by adding the same nodes and
+/// edges in a different order compare equal.
+///
+/// Computes in **O(|V| + |E|)** time (average).
+impl<N, E, Ty, S> PartialEq for GraphMap<N, E, Ty, S>
+where
+    N: NodeTrait,
+    E: PartialEq,
+    Ty: EdgeType,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.node_count() == other.node_count()
+            && self.edge_count() == other.edge_count()
+            && self.nodes.keys().all(|n| other.nodes.contains_key(n))
+            && self
+                .edges
+                .iter()
+                .all(|(key, weight)| other.edges.get(key) == Some(weight))
+    }
+}
+
+impl<N, E, Ty, S> Eq for GraphMap<N, E, Ty, S>
+where
+    N: NodeTrait,
+    E: Eq,
+    Ty: EdgeType,
+    S: BuildHasher,
+{
+}
+
+/// Hash the graph by structure, consistently with its `PartialEq`
+/// implementation.
+///
+/// Insertion order is ignored: the node and edge hashes are combined with a
+/// commutative operation.
+///
+/// Computes in **O(|V| + |E|)** time.
+impl<N, E, Ty, S> Hash for GraphMap<N, E, Ty, S>
+where
+    N: NodeTrait,
+    E: Hash,
+    Ty: EdgeType,
+    S: BuildHasher,
+{
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        fn hash_one<T: Hash>(value: T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+        let nodes = self
+            .nodes
+            .keys()
+            .fold(0u64, |acc, n| acc.wrapping_add(hash_one(n)));
+        let edges = self
+            .edges
+            .iter()
+            .fold(0u64, |acc, edge| acc.wrapping_add(hash_one(edge)));
+        state.write_usize(self.node_count());
+        state.write_u64(nodes);
+        state.write_usize(self.edge_count());
+        state.write_u64(edges);
+    }
+}
+
+/// A reference that is hashed and compared by its pointer value.
+///
+/// `Ptr` is used for certain configurations of `GraphMap`,
+/// in particular in the combination where the node type for
+/// `GraphMap` is something of type for example `Ptr(&Cell<T>)`,
+/// with the `Cell<T>` being `TypedArena` allocated.
+pub struct Ptr<'b, T: 'b>(pub &'b T);
+
+impl<'b, T> Copy for Ptr<'b, T> {}
+impl<'b, T> Clone for Ptr<'b, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+fn ptr_eq<T>(a: *const T, b: *const T) -> bool {
+    a == b
+}
+
+impl<'b, T> PartialEq for Ptr<'b, T> {
+    /// Ptr compares by pointer equality, i.e if they point to the same value
+    fn eq(&self, other: &Ptr<'b, T>) -> bool {
+        ptr_eq(self.0, other.0)
+    }
+}
+
+impl<'b, T> PartialOrd for Ptr<'b, T> {
+    fn partial_cmp(&self, other: &Ptr<'b, T>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'b, T> Ord for Ptr<'b, T> {
+    /// Ptr is ordered by pointer value, i.e. an arbitrary but stable and total order.
+    fn cmp(&self, other: &Ptr<'b, T>) -> Ordering {
+        let a: *const T = self.0;
+        let b: *const T = other.0;
+        a.cmp(&b)
+    }
+}
+
+impl<'b, T> Deref for Ptr<'b, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        self.0
+    }
+}
+
+impl<'b, T> Eq for Ptr<'b, T> {}
+
+impl<'b, T> Hash for Ptr<'b, T> {
+    fn hash<H: hash::Hasher>(&self, st: &mut H) {
+        let ptr = (self.0) as *const T;
+        ptr.hash(st)
+    }
+}
+
+impl<'b, T: fmt::Debug> fmt::Debug for Ptr<'b, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<'a, N, E: 'a, Ty, S> IntoNodeIdentifiers for &'a GraphMap<N, E, Ty, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    S: BuildHasher,
+{
+    type NodeIdentifiers = NodeIdentifiers<'a, N, E, Ty>;
+
+    fn node_identifiers(self) -> Self::NodeIdentifiers {
+        NodeIdentifiers {
+            iter: self.nodes.iter(),
+            ty: self.ty,
+            edge_ty: PhantomData,
+        }
+    }
+}
+
+impl<N, E, Ty, S> NodeCount for GraphMap<N, E, Ty, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    S: BuildHasher,
+{
+    fn node_count(&self) -> usize {
+        (*self).node_count()
+    }
+}
+
+pub struct NodeIdentifiers<'a, N, E: 'a, Ty>
+where
+    N: 'a + NodeTrait,
+{
+    iter: IndexMapIter<'a, N, Vec<(N, CompactDirection)>>,
+    ty: PhantomData<Ty>,
+    edge_ty: PhantomData<E>,
+}
+
+impl<'a, N, E, Ty> Iterator for NodeIdentifiers<'a, N, E, Ty>
+where
+    N: 'a + NodeTrait,
+    E: 'a,
+    Ty: EdgeType,
+{
+    type Item = N;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(&n, _)| n)
+    }
+}
+
+impl<'a, N, E, Ty, S> IntoNodeReferences for &'a GraphMap<N, E, Ty, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    S: BuildHasher,
+{
+    type NodeRef = (N, &'a N);
+    type NodeReferences = NodeReferences<'a, N, E, Ty>;
+    fn node_references(self) -> Self::NodeReferences {
+        NodeReferences {
+            iter: self.nodes.iter(),
+            ty: self.ty,
+            edge_ty: PhantomData,
+        }
+    }
+}
+
+pub struct NodeReferences<'a, N, E: 'a, Ty>
+where
+    N: 'a + NodeTrait,
+{
+    iter: IndexMapIter<'a, N, Vec<(N, CompactDirection)>>,
+    ty: PhantomData<Ty>,
+    edge_ty: PhantomData<E>,
+}
+
+impl<'a, N, E, Ty> Iterator for NodeReferences<'a, N, E, Ty>
+where
+    N: 'a + NodeTrait,
+    E: 'a,
+    Ty: EdgeType,
+{
+    type Item = (N, &'a N);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(n, _)| (*n, n))
+    }
+}
+
+impl<N, E, Ty, S> NodeIndexable for GraphMap<N, E, Ty, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    S: BuildHasher,
+{
+    fn node_bound(&self) -> usize {
+        self.node_count()
+    }
+    fn to_index(&self, ix: Self::NodeId) -> usize {
+        let (i, _, _) = self.nodes.get_full(&ix).unwrap();
+        i
+    }
+    fn from_index(&self, ix: usize) -> Self::NodeId {
+        let (&key, _) = self.nodes.get_index(ix).unwrap();
+        key
+    }
+}
+
+impl<N, E, Ty, S> NodeCompactIndexable for GraphMap<N, E, Ty, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    S: BuildHasher,
+{
+}
@@ -0,0 +1,580 @@
+//! `MultiGraphMap<N, E, Ty>` is a `GraphMap` variant that allows parallel
+//! edges.
+
+use indexmap::map::Iter as IndexMapIter;
+use indexmap::IndexMap;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::slice::Iter;
+
+use crate::{Directed, Direction, EdgeType, Incoming, Outgoing, Undirected};
+
+use super::{CompactDirection, Neighbors, NodeTrait, Nodes};
+use crate::IntoWeightedEdge;
+
+/// A `MultiGraphMap` with undirected edges.
+pub type UnMultiGraphMap<N, E> = MultiGraphMap<N, E, Undirected>;
+/// A `MultiGraphMap` with directed edges.
+pub type DiMultiGraphMap<N, E> = MultiGraphMap<N, E, Directed>;
+
+/// An opaque handle to a single edge of a `MultiGraphMap`.
+///
+/// Handles stay valid until the edge they refer to is removed, even if
+/// other edges between the same pair of nodes are added or removed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EdgeHandle<N> {
+    key: (N, N),
+    id: usize,
+}
+
+impl<N: Copy> EdgeHandle<N> {
+    /// Return the endpoints of the edge, using the canonical orientation
+    /// under which it is stored.
+    pub fn endpoints(&self) -> (N, N) {
+        self.key
+    }
+}
+
+/// `MultiGraphMap<N, E, Ty>` is a graph datastructure using an associative
+/// array of its node weights `N`, like `GraphMap`, but which allows
+/// *parallel edges*: several edges connecting the same pair of nodes.
+///
+/// Each node pair maps to the list of edges between them, so testing
+/// whether two nodes are connected is still a constant time operation.
+/// Adding an edge returns an `EdgeHandle`, which is used to access or
+/// remove that particular edge later.
+///
+/// For undirected graphs, the node pair is stored in the same canonical
+/// (ordered) form as in `GraphMap`.
+///
+/// The node identifiers are hashed with `S`, like in `GraphMap`.
+///
+/// Depends on crate feature `graphmap` (default).
+#[derive(Clone)]
+pub struct MultiGraphMap<N, E, Ty, S = RandomState> {
+    nodes: IndexMap<N, Vec<(N, CompactDirection)>, S>,
+    edges: IndexMap<(N, N), Vec<(usize, E)>, S>,
+    edge_count: usize,
+    next_id: usize,
+    ty: PhantomData<Ty>,
+}
+
+impl<N: Eq + Hash + fmt::Debug, E: fmt::Debug, Ty: EdgeType, S: BuildHasher> fmt::Debug
+    for MultiGraphMap<N, E, Ty, S>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.nodes.fmt(f)
+    }
+}
+
+impl<N, E, Ty, S> MultiGraphMap<N, E, Ty, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    S: BuildHasher,
+{
+    /// Create a new `MultiGraphMap`
+    pub fn new() -> Self
+    where
+        S: Default,
+    {
+        Self::default()
+    }
+
+    /// Create a new `MultiGraphMap` with estimated capacity.
+    ///
+    /// The edge capacity is the number of distinct node pairs.
+    pub fn with_capacity(nodes: usize, edges: usize) -> Self
+    where
+        S: Default,
+    {
+        MultiGraphMap {
+            nodes: IndexMap::with_capacity_and_hasher(nodes, S::default()),
+            edges: IndexMap::with_capacity_and_hasher(edges, S::default()),
+            edge_count: 0,
+            next_id: 0,
+            ty: PhantomData,
+        }
+    }
+
+    /// Create a new `MultiGraphMap` with estimated capacity, that uses
+    /// `hasher` to hash the node identifiers.
+    ///
+    /// ```
+    /// use petgraph::graphmap::MultiGraphMap;
+    /// use petgraph::Undirected;
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let mut g =
+    ///     MultiGraphMap::<_, _, Undirected, _>::with_capacity_and_hasher(2, 1, RandomState::new());
+    /// g.add_edge(1, 2, 'a');
+    /// g.add_edge(2, 1, 'b');
+    /// assert_eq!(g.edge_multiplicity(1, 2), 2);
+    /// ```
+    pub fn with_capacity_and_hasher(nodes: usize, edges: usize, hasher: S) -> Self
+    where
+        S: Clone,
+    {
+        MultiGraphMap {
+            nodes: IndexMap::with_capacity_and_hasher(nodes, hasher.clone()),
+            edges: IndexMap::with_capacity_and_hasher(edges, hasher),
+            edge_count: 0,
+            next_id: 0,
+            ty: PhantomData,
+        }
+    }
+
+    /// Return a reference to the graph's hash builder.
+    pub fn hasher(&self) -> &S {
+        self.nodes.hasher()
+    }
+
+    /// Return the current node and edge capacity of the graph.
+    pub fn capacity(&self) -> (usize, usize) {
+        (self.nodes.capacity(), self.edges.capacity())
+    }
+
+    /// Use their natural order to map the node pair (a, b) to a canonical edge id.
+    #[inline]
+    fn edge_key(a: N, b: N) -> (N, N) {
+        if Ty::is_directed() || a <= b {
+            (a, b)
+        } else {
+            (b, a)
+        }
+    }
+
+    /// Whether the graph has directed edges.
+    pub fn is_directed(&self) -> bool {
+        Ty::is_directed()
+    }
+
+    /// Create a new `MultiGraphMap` from an iterable of edges.
+    ///
+    /// Repeated edges in the list are all inserted, as parallel edges.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnMultiGraphMap;
+    ///
+    /// let gr = UnMultiGraphMap::<_, ()>::from_edges(&[
+    ///     (0, 1), (0, 1), (1, 2),
+    /// ]);
+    /// assert_eq!(gr.edge_count(), 3);
+    /// ```
+    pub fn from_edges<I>(iterable: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: IntoWeightedEdge<E, NodeId = N>,
+        S: Default,
+    {
+        Self::from_iter(iterable)
+    }
+
+    /// Return the number of nodes in the graph.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Return the number of edges in the graph, counting every parallel
+    /// edge.
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Remove all nodes and edges
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.edges.clear();
+        self.edge_count = 0;
+    }
+
+    /// Add node `n` to the graph.
+    pub fn add_node(&mut self, n: N) -> N {
+        self.nodes.entry(n).or_default();
+        n
+    }
+
+    /// Return `true` if node `n` was removed.
+    ///
+    /// Computes in **O(V)** time, due to the removal of edges with other nodes.
+    pub fn remove_node(&mut self, n: N) -> bool {
+        let links = match self.nodes.swap_remove(&n) {
+            None => return false,
+            Some(sus) => sus,
+        };
+        for (succ, dir) in links {
+            let edge = if dir == CompactDirection::Outgoing {
+                Self::edge_key(n, succ)
+            } else {
+                Self::edge_key(succ, n)
+            };
+            // remove all successor links
+            self.remove_single_edge(&succ, &n, dir.opposite());
+            // Remove all edge values
+            if let Some(parallel) = self.edges.swap_remove(&edge) {
+                self.edge_count -= parallel.len();
+            }
+        }
+        true
+    }
+
+    /// Return `true` if the node is contained in the graph.
+    pub fn contains_node(&self, n: N) -> bool {
+        self.nodes.contains_key(&n)
+    }
+
+    /// Add an edge connecting `a` and `b` to the graph, with associated
+    /// data `weight`. For a directed graph, the edge is directed from `a`
+    /// to `b`.
+    ///
+    /// Inserts nodes `a` and/or `b` if they aren't already part of the graph.
+    ///
+    /// The edge is added even if `a` and `b` are already connected, and a
+    /// handle to the new edge is returned.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiMultiGraphMap;
+    ///
+    /// let mut g = DiMultiGraphMap::new();
+    /// let line1 = g.add_edge("x", "y", 1);
+    /// let line2 = g.add_edge("x", "y", 2);
+    /// assert_eq!(g.edge_count(), 2);
+    /// assert_eq!(g.remove_edge(line1), Some(1));
+    /// assert_eq!(g.edge_weight(line2), Some(&2));
+    /// assert!(g.contains_edge("x", "y"));
+    /// ```
+    pub fn add_edge(&mut self, a: N, b: N, weight: E) -> EdgeHandle<N> {
+        let key = Self::edge_key(a, b);
+        let id = self.next_id;
+        self.next_id += 1;
+        self.edge_count += 1;
+        let parallel = self.edges.entry(key).or_default();
+        parallel.push((id, weight));
+        if parallel.len() == 1 {
+            // insert in the adjacency list if it's a new node pair
+            self.nodes
+                .entry(a)
+                .or_insert_with(|| Vec::with_capacity(1))
+                .push((b, CompactDirection::Outgoing));
+            if a != b {
+                // self loops don't have the Incoming entry
+                self.nodes
+                    .entry(b)
+                    .or_insert_with(|| Vec::with_capacity(1))
+                    .push((a, CompactDirection::Incoming));
+            }
+        }
+        EdgeHandle { key, id }
+    }
+
+    /// Remove edge relation from a to b
+    ///
+    /// Return `true` if it did exist.
+    fn remove_single_edge(&mut self, a: &N, b: &N, dir: Direction) -> bool {
+        match self.nodes.get_mut(a) {
+            None => false,
+            Some(sus) => {
+                let position = if Ty::is_directed() {
+                    sus.iter()
+                        .position(|elt| elt == &(*b, CompactDirection::from(dir)))
+                } else {
+                    sus.iter().position(|elt| &elt.0 == b)
+                };
+                match position {
+                    Some(index) => {
+                        sus.swap_remove(index);
+                        true
+                    }
+                    None => false,
+                }
+            }
+        }
+    }
+
+    /// Remove the edge `edge` from the graph and return its weight.
+    ///
+    /// Return `None` if the edge didn't exist.
+    pub fn remove_edge(&mut self, edge: EdgeHandle<N>) -> Option<E> {
+        let (a, b) = edge.key;
+        let (weight, now_empty) = {
+            let parallel = self.edges.get_mut(&edge.key)?;
+            let index = parallel.iter().position(|&(id, _)| id == edge.id)?;
+            let (_, weight) = parallel.remove(index);
+            (weight, parallel.is_empty())
+        };
+        self.edge_count -= 1;
+        if now_empty {
+            // the last edge between a and b is gone
+            self.edges.swap_remove(&edge.key);
+            let exist1 = self.remove_single_edge(&a, &b, Outgoing);
+            let exist2 = if a != b {
+                self.remove_single_edge(&b, &a, Incoming)
+            } else {
+                exist1
+            };
+            debug_assert!(exist1 && exist2);
+        }
+        Some(weight)
+    }
+
+    /// Return `true` if at least one edge connecting `a` with `b` is
+    /// contained in the graph.
+    pub fn contains_edge(&self, a: N, b: N) -> bool {
+        self.edges.contains_key(&Self::edge_key(a, b))
+    }
+
+    /// Return the number of parallel edges connecting `a` with `b`.
+    pub fn edge_multiplicity(&self, a: N, b: N) -> usize {
+        self.edges
+            .get(&Self::edge_key(a, b))
+            .map_or(0, |parallel| parallel.len())
+    }
+
+    /// Return an iterator over the nodes of the graph.
+    ///
+    /// Iterator element type is `N`.
+    pub fn nodes(&self) -> Nodes<'_, N> {
+        Nodes {
+            iter: self.nodes.keys().cloned(),
+        }
+    }
+
+    /// Return an iterator of all nodes with an edge starting from `a`.
+    ///
+    /// Each neighbor is produced once, no matter how many parallel edges
+    /// connect it with `a`.
+    ///
+    /// - `Directed`: Outgoing edges from `a`.
+    /// - `Undirected`: All edges from or to `a`.
+    ///
+    /// Produces an empty iterator if the node doesn't exist.<br>
+    /// Iterator element type is `N`.
+    pub fn neighbors(&self, a: N) -> Neighbors<'_, N, Ty> {
+        Neighbors {
+            iter: match self.nodes.get(&a) {
+                Some(neigh) => neigh.iter(),
+                None => [].iter(),
+            },
+            ty: self.ty,
+        }
+    }
+
+    /// Return an iterator of target nodes with an edge starting from `a`,
+    /// paired with their respective edge weights.
+    ///
+    /// Every parallel edge is produced.
+    ///
+    /// - `Directed`: Outgoing edges from `a`.
+    /// - `Undirected`: All edges from or to `a`.
+    ///
+    /// Produces an empty iterator if the node doesn't exist.<br>
+    /// Iterator element type is `(N, N, &E)`.
+    pub fn edges(&self, from: N) -> MultiEdges<'_, N, E, Ty, S> {
+        MultiEdges {
+            from,
+            to: from,
+            iter: self.neighbors(from),
+            edges: &self.edges,
+            parallel: [].iter(),
+        }
+    }
+
+    /// Return an iterator over the edges connecting `a` with `b`, paired
+    /// with their handles.
+    ///
+    /// Iterator element type is `(EdgeHandle<N>, &E)`.
+    pub fn edges_connecting(&self, a: N, b: N) -> EdgesConnecting<'_, N, E> {
+        let key = Self::edge_key(a, b);
+        EdgesConnecting {
+            key,
+            iter: match self.edges.get(&key) {
+                Some(parallel) => parallel.iter(),
+                None => [].iter(),
+            },
+        }
+    }
+
+    /// Return a reference to the weight of `edge`, or `None` if the edge
+    /// does not exist in the graph.
+    pub fn edge_weight(&self, edge: EdgeHandle<N>) -> Option<&E> {
+        self.edges
+            .get(&edge.key)?
+            .iter()
+            .find(|&&(id, _)| id == edge.id)
+            .map(|(_, weight)| weight)
+    }
+
+    /// Return a mutable reference to the weight of `edge`, or `None` if the
+    /// edge does not exist in the graph.
+    pub fn edge_weight_mut(&mut self, edge: EdgeHandle<N>) -> Option<&mut E> {
+        self.edges
+            .get_mut(&edge.key)?
+            .iter_mut()
+            .find(|&&mut (id, _)| id == edge.id)
+            .map(|(_, weight)| weight)
+    }
+
+    /// Return an iterator over all edges of the graph with their weight in
+    /// arbitrary order.
+    ///
+    /// Every parallel edge is produced.
+    ///
+    /// Iterator element type is `(N, N, &E)`
+    pub fn all_edges(&self) -> MultiAllEdges<'_, N, E, Ty> {
+        MultiAllEdges {
+            inner: self.edges.iter(),
+            key: None,
+            parallel: [].iter(),
+            ty: self.ty,
+        }
+    }
+}
+
+/// Create a new `MultiGraphMap` from an iterable of edges.
+impl<N, E, Ty, S, Item> FromIterator<Item> for MultiGraphMap<N, E, Ty, S>
+where
+    Item: IntoWeightedEdge<E, NodeId = N>,
+    N: NodeTrait,
+    Ty: EdgeType,
+    S: BuildHasher + Default,
+{
+    fn from_iter<I>(iterable: I) -> Self
+    where
+        I: IntoIterator<Item = Item>,
+    {
+        let iter = iterable.into_iter();
+        let (low, _) = iter.size_hint();
+        let mut g = Self::with_capacity(0, low);
+        g.extend(iter);
+        g
+    }
+}
+
+/// Extend the graph from an iterable of edges.
+///
+/// Nodes are inserted automatically to match the edges.
+impl<N, E, Ty, S, Item> Extend<Item> for MultiGraphMap<N, E, Ty, S>
+where
+    Item: IntoWeightedEdge<E, NodeId = N>,
+    N: NodeTrait,
+    Ty: EdgeType,
+    S: BuildHasher,
+{
+    fn extend<I>(&mut self, iterable: I)
+    where
+        I: IntoIterator<Item = Item>,
+    {
+        for elt in iterable {
+            let (source, target, weight) = elt.into_weighted_edge();
+            self.add_edge(source, target, weight);
+        }
+    }
+}
+
+/// Create a new empty `MultiGraphMap`.
+impl<N, E, Ty, S> Default for MultiGraphMap<N, E, Ty, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    S: BuildHasher + Default,
+{
+    fn default() -> Self {
+        MultiGraphMap::with_capacity(0, 0)
+    }
+}
+
+pub struct MultiEdges<'a, N, E: 'a, Ty, S = RandomState>
+where
+    N: 'a + NodeTrait,
+    Ty: EdgeType,
+    S: 'a + BuildHasher,
+{
+    from: N,
+    to: N,
+    edges: &'a IndexMap<(N, N), Vec<(usize, E)>, S>,
+    iter: Neighbors<'a, N, Ty>,
+    parallel: Iter<'a, (usize, E)>,
+}
+
+impl<'a, N, E, Ty, S> Iterator for MultiEdges<'a, N, E, Ty, S>
+where
+    N: 'a + NodeTrait,
+    E: 'a,
+    Ty: EdgeType,
+    S: BuildHasher,
+{
+    type Item = (N, N, &'a E);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((_, weight)) = self.parallel.next() {
+                return Some((self.from, self.to, weight));
+            }
+            let b = self.iter.next()?;
+            self.to = b;
+            self.parallel = match self
+                .edges
+                .get(&MultiGraphMap::<N, E, Ty, S>::edge_key(self.from, b))
+            {
+                None => unreachable!(),
+                Some(parallel) => parallel.iter(),
+            };
+        }
+    }
+}
+
+pub struct EdgesConnecting<'a, N, E: 'a> {
+    key: (N, N),
+    iter: Iter<'a, (usize, E)>,
+}
+
+impl<'a, N, E> Iterator for EdgesConnecting<'a, N, E>
+where
+    N: 'a + NodeTrait,
+    E: 'a,
+{
+    type Item = (EdgeHandle<N>, &'a E);
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = self.key;
+        self.iter
+            .next()
+            .map(|&(id, ref weight)| (EdgeHandle { key, id }, weight))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub struct MultiAllEdges<'a, N, E: 'a, Ty>
+where
+    N: 'a + NodeTrait,
+{
+    inner: IndexMapIter<'a, (N, N), Vec<(usize, E)>>,
+    key: Option<(N, N)>,
+    parallel: Iter<'a, (usize, E)>,
+    ty: PhantomData<Ty>,
+}
+
+impl<'a, N, E, Ty> Iterator for MultiAllEdges<'a, N, E, Ty>
+where
+    N: 'a + NodeTrait,
+    E: 'a,
+    Ty: EdgeType,
+{
+    type Item = (N, N, &'a E);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((a, b)) = self.key {
+                if let Some((_, weight)) = self.parallel.next() {
+                    return Some((a, b, weight));
+                }
+            }
+            let (&key, parallel) = self.inner.next()?;
+            self.key = Some(key);
+            self.parallel = parallel.iter();
+        }
+    }
+}
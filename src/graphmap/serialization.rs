@@ -0,0 +1,146 @@
+use serde::de::Error;
+
+use indexmap::IndexMap;
+use std::hash::BuildHasher;
+
+use crate::serde_utils::CollectSeqWithLength;
+use crate::serde_utils::{FromDeserialized, IntoSerializable};
+use crate::EdgeType;
+
+use super::{CompactDirection, GraphMap, NodeTrait};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Serialization representation for GraphMap
+/// Keep in sync with deserialization
+///
+/// The serialization format is as follows, in Pseudorust:
+///
+/// GraphMap {
+///     nodes: [N],
+///     edges: [(N, N, E)]
+/// }
+///
+/// Edges are stored with their endpoints in the canonical order used
+/// by the graph, so for undirected graphs the source is never greater
+/// than the target.
+#[derive(Serialize)]
+#[serde(rename = "GraphMap")]
+#[serde(bound(serialize = "N: NodeTrait + Serialize, E: Serialize"))]
+pub struct SerGraphMap<'a, N: 'a, E: 'a, H: 'a> {
+    #[serde(serialize_with = "ser_graphmap_nodes")]
+    nodes: &'a IndexMap<N, Vec<(N, CompactDirection)>, H>,
+    #[serde(serialize_with = "ser_graphmap_edges")]
+    edges: &'a IndexMap<(N, N), E, H>,
+}
+
+// Deserialization representation for GraphMap
+// Keep in sync with serialization
+#[derive(Deserialize)]
+#[serde(rename = "GraphMap")]
+#[serde(bound(deserialize = "N: Deserialize<'de>, E: Deserialize<'de>"))]
+pub struct DeserGraphMap<N, E> {
+    nodes: Vec<N>,
+    edges: Vec<(N, N, E)>,
+}
+
+fn ser_graphmap_nodes<S, N, H>(
+    nodes: &&IndexMap<N, Vec<(N, CompactDirection)>, H>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    N: NodeTrait + Serialize,
+{
+    serializer.collect_seq_exact(nodes.keys())
+}
+
+fn ser_graphmap_edges<S, N, E, H>(
+    edges: &&IndexMap<(N, N), E, H>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    N: NodeTrait + Serialize,
+    E: Serialize,
+{
+    serializer.collect_seq_exact(edges.iter().map(|(&(a, b), weight)| (a, b, weight)))
+}
+
+impl<'a, N, E, Ty, H> IntoSerializable for &'a GraphMap<N, E, Ty, H>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    H: BuildHasher,
+{
+    type Output = SerGraphMap<'a, N, E, H>;
+    fn into_serializable(self) -> Self::Output {
+        SerGraphMap {
+            nodes: &self.nodes,
+            edges: &self.edges,
+        }
+    }
+}
+
+/// Requires crate feature `"serde-1"`
+impl<N, E, Ty, H> Serialize for GraphMap<N, E, Ty, H>
+where
+    Ty: EdgeType,
+    N: NodeTrait + Serialize,
+    E: Serialize,
+    H: BuildHasher,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.into_serializable().serialize(serializer)
+    }
+}
+
+impl<N, E, Ty, H> FromDeserialized for GraphMap<N, E, Ty, H>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    H: BuildHasher + Default,
+{
+    type Input = DeserGraphMap<N, E>;
+    fn from_deserialized<E2>(input: Self::Input) -> Result<Self, E2>
+    where
+        E2: Error,
+    {
+        let mut gr = GraphMap::with_capacity(input.nodes.len(), input.edges.len());
+        for node in input.nodes {
+            if gr.contains_node(node) {
+                Err(E2::custom("invalid value: duplicate node in graph"))?
+            }
+            gr.add_node(node);
+        }
+        for (a, b, weight) in input.edges {
+            if !gr.contains_node(a) || !gr.contains_node(b) {
+                Err(E2::custom(
+                    "invalid value: edge endpoint does not exist in graph",
+                ))?
+            }
+            if gr.add_edge(a, b, weight).is_some() {
+                Err(E2::custom("invalid value: duplicate edge in graph"))?
+            }
+        }
+        Ok(gr)
+    }
+}
+
+/// Requires crate feature `"serde-1"`
+impl<'de, N, E, Ty, H> Deserialize<'de> for GraphMap<N, E, Ty, H>
+where
+    Ty: EdgeType,
+    N: NodeTrait + Deserialize<'de>,
+    E: Deserialize<'de>,
+    H: BuildHasher + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::from_deserialized(DeserGraphMap::deserialize(deserializer)?)
+    }
+}
@@ -0,0 +1,313 @@
+//! A `GraphMap` view with contiguous node indices.
+
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::marker::PhantomData;
+use std::ops::Range;
+
+use fixedbitset::FixedBitSet;
+
+use crate::graph::{node_index, DefaultIx, IndexType, NodeIndex};
+use crate::visit::{
+    Data, GraphBase, GraphProp, GraphRef, IntoEdgeReferences, IntoEdges, IntoNeighbors,
+    IntoNodeIdentifiers, NodeCompactIndexable, NodeCount, NodeIndexable, Visitable,
+};
+use crate::EdgeType;
+
+use super::{AllEdges, Edges, GraphMap, Neighbors, NodeTrait};
+
+/// A borrowed view of a `GraphMap` that identifies nodes by `NodeIndex`, like
+/// a `Graph`, created by `GraphMap::graph_view`.
+///
+/// The node with index `i` is the `i`th node in the iteration order of
+/// `.nodes()`, and edges are identified by their endpoints. Edge weights are
+/// borrowed from the `GraphMap`.
+pub struct GraphView<'a, N: 'a, E: 'a, Ty: 'a, Ix = DefaultIx, S: 'a = RandomState> {
+    graph: &'a GraphMap<N, E, Ty, S>,
+    ix: PhantomData<Ix>,
+}
+
+impl<'a, N, E, Ty, Ix, S> Clone for GraphView<'a, N, E, Ty, Ix, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, N, E, Ty, Ix, S> Copy for GraphView<'a, N, E, Ty, Ix, S> {}
+
+impl<'a, N, E, Ty, Ix, S> GraphView<'a, N, E, Ty, Ix, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    Ix: IndexType,
+    S: BuildHasher,
+{
+    pub(super) fn new(graph: &'a GraphMap<N, E, Ty, S>) -> Self {
+        GraphView {
+            graph,
+            ix: PhantomData,
+        }
+    }
+
+    /// Return the underlying `GraphMap`.
+    pub fn graph(&self) -> &'a GraphMap<N, E, Ty, S> {
+        self.graph
+    }
+
+    /// Return the index of node `n`, or `None` if it doesn't exist.
+    pub fn node_index(&self, n: N) -> Option<NodeIndex<Ix>> {
+        self.graph.nodes.get_index_of(&n).map(node_index)
+    }
+
+    /// Return the node with index `ix`, or `None` if it doesn't exist.
+    pub fn node(&self, ix: NodeIndex<Ix>) -> Option<N> {
+        self.graph.nodes.get_index(ix.index()).map(|(&n, _)| n)
+    }
+
+    fn index_of(&self, n: N) -> NodeIndex<Ix> {
+        node_index(self.graph.nodes.get_index_of(&n).unwrap())
+    }
+
+    fn node_at(&self, ix: NodeIndex<Ix>) -> N {
+        *self.graph.nodes.get_index(ix.index()).unwrap().0
+    }
+}
+
+impl<'a, N, E, Ty, Ix, S> GraphBase for GraphView<'a, N, E, Ty, Ix, S>
+where
+    Ix: IndexType,
+{
+    type NodeId = NodeIndex<Ix>;
+    type EdgeId = (NodeIndex<Ix>, NodeIndex<Ix>);
+}
+
+impl<'a, N, E, Ty, Ix, S> GraphRef for GraphView<'a, N, E, Ty, Ix, S> where Ix: IndexType {}
+
+impl<'a, N, E, Ty, Ix, S> Data for GraphView<'a, N, E, Ty, Ix, S>
+where
+    Ix: IndexType,
+{
+    type NodeWeight = N;
+    type EdgeWeight = E;
+}
+
+impl<'a, N, E, Ty, Ix, S> GraphProp for GraphView<'a, N, E, Ty, Ix, S>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    type EdgeType = Ty;
+}
+
+impl<'a, N, E, Ty, Ix, S> NodeCount for GraphView<'a, N, E, Ty, Ix, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    Ix: IndexType,
+    S: BuildHasher,
+{
+    fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+}
+
+impl<'a, N, E, Ty, Ix, S> NodeIndexable for GraphView<'a, N, E, Ty, Ix, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    Ix: IndexType,
+    S: BuildHasher,
+{
+    fn node_bound(&self) -> usize {
+        self.graph.node_count()
+    }
+    fn to_index(&self, ix: Self::NodeId) -> usize {
+        ix.index()
+    }
+    fn from_index(&self, ix: usize) -> Self::NodeId {
+        node_index(ix)
+    }
+}
+
+impl<'a, N, E, Ty, Ix, S> NodeCompactIndexable for GraphView<'a, N, E, Ty, Ix, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    Ix: IndexType,
+    S: BuildHasher,
+{
+}
+
+impl<'a, N, E, Ty, Ix, S> Visitable for GraphView<'a, N, E, Ty, Ix, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    Ix: IndexType,
+    S: BuildHasher,
+{
+    type Map = FixedBitSet;
+    fn visit_map(&self) -> FixedBitSet {
+        FixedBitSet::with_capacity(self.graph.node_count())
+    }
+    fn reset_map(&self, map: &mut Self::Map) {
+        map.clear();
+        map.grow(self.graph.node_count());
+    }
+}
+
+impl<'a, N, E, Ty, Ix, S> IntoNodeIdentifiers for GraphView<'a, N, E, Ty, Ix, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    Ix: IndexType,
+    S: BuildHasher,
+{
+    type NodeIdentifiers = ViewNodeIdentifiers<Ix>;
+    fn node_identifiers(self) -> Self::NodeIdentifiers {
+        ViewNodeIdentifiers {
+            range: 0..self.graph.node_count(),
+            ix: PhantomData,
+        }
+    }
+}
+
+impl<'a, N, E, Ty, Ix, S> IntoNeighbors for GraphView<'a, N, E, Ty, Ix, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    Ix: IndexType,
+    S: BuildHasher,
+{
+    type Neighbors = ViewNeighbors<'a, N, E, Ty, Ix, S>;
+    fn neighbors(self, a: Self::NodeId) -> Self::Neighbors {
+        ViewNeighbors {
+            view: self,
+            iter: self.graph.neighbors(self.node_at(a)),
+        }
+    }
+}
+
+impl<'a, N, E, Ty, Ix, S> IntoEdgeReferences for GraphView<'a, N, E, Ty, Ix, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    Ix: IndexType,
+    S: BuildHasher,
+{
+    type EdgeRef = (NodeIndex<Ix>, NodeIndex<Ix>, &'a E);
+    type EdgeReferences = ViewEdgeReferences<'a, N, E, Ty, Ix, S>;
+    fn edge_references(self) -> Self::EdgeReferences {
+        ViewEdgeReferences {
+            view: self,
+            iter: self.graph.all_edges(),
+        }
+    }
+}
+
+impl<'a, N, E, Ty, Ix, S> IntoEdges for GraphView<'a, N, E, Ty, Ix, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    Ix: IndexType,
+    S: BuildHasher,
+{
+    type Edges = ViewEdges<'a, N, E, Ty, Ix, S>;
+    fn edges(self, a: Self::NodeId) -> Self::Edges {
+        ViewEdges {
+            view: self,
+            iter: self.graph.edges(self.node_at(a)),
+        }
+    }
+}
+
+pub struct ViewNodeIdentifiers<Ix> {
+    range: Range<usize>,
+    ix: PhantomData<Ix>,
+}
+
+impl<Ix> Iterator for ViewNodeIdentifiers<Ix>
+where
+    Ix: IndexType,
+{
+    type Item = NodeIndex<Ix>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(node_index)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+pub struct ViewNeighbors<'a, N: 'a, E: 'a, Ty: 'a, Ix, S: 'a>
+where
+    Ty: EdgeType,
+{
+    view: GraphView<'a, N, E, Ty, Ix, S>,
+    iter: Neighbors<'a, N, Ty>,
+}
+
+impl<'a, N, E, Ty, Ix, S> Iterator for ViewNeighbors<'a, N, E, Ty, Ix, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    Ix: IndexType,
+    S: BuildHasher,
+{
+    type Item = NodeIndex<Ix>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let n = self.iter.next()?;
+        Some(self.view.index_of(n))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub struct ViewEdges<'a, N: 'a, E: 'a, Ty: 'a, Ix, S: 'a>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    S: BuildHasher,
+{
+    view: GraphView<'a, N, E, Ty, Ix, S>,
+    iter: Edges<'a, N, E, Ty, S>,
+}
+
+impl<'a, N, E, Ty, Ix, S> Iterator for ViewEdges<'a, N, E, Ty, Ix, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    Ix: IndexType,
+    S: BuildHasher,
+{
+    type Item = (NodeIndex<Ix>, NodeIndex<Ix>, &'a E);
+    fn next(&mut self) -> Option<Self::Item> {
+        let (a, b, weight) = self.iter.next()?;
+        Some((self.view.index_of(a), self.view.index_of(b), weight))
+    }
+}
+
+pub struct ViewEdgeReferences<'a, N: 'a, E: 'a, Ty: 'a, Ix, S: 'a>
+where
+    N: NodeTrait,
+{
+    view: GraphView<'a, N, E, Ty, Ix, S>,
+    iter: AllEdges<'a, N, E, Ty>,
+}
+
+impl<'a, N, E, Ty, Ix, S> Iterator for ViewEdgeReferences<'a, N, E, Ty, Ix, S>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    Ix: IndexType,
+    S: BuildHasher,
+{
+    type Item = (NodeIndex<Ix>, NodeIndex<Ix>, &'a E);
+    fn next(&mut self) -> Option<Self::Item> {
+        let (a, b, weight) = self.iter.next()?;
+        Some((self.view.index_of(a), self.view.index_of(b), weight))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
@@ -93,7 +93,7 @@
 //! # Crate features
 //!
 //! * **serde-1** -
-//!   Defaults off. Enables serialization for ``Graph, StableGraph`` using
+//!   Defaults off. Enables serialization for ``Graph, StableGraph, GraphMap`` using
 //!   [`serde 1.0`](https://crates.io/crates/serde). May require a more recent version
 //!   of Rust than petgraph alone.
 //! * **graphmap** -
@@ -102,6 +102,12 @@
 //!   Defaults on. Enables [`StableGraph`](./stable_graph/struct.StableGraph.html).
 //! * **matrix_graph** -
 //!   Defaults on. Enables [`MatrixGraph`](./matrix_graph/struct.MatrixGraph.html).
+//! * **rand** -
+//!   Defaults off. Enables random graph generation for ``GraphMap`` using
+//!   [`rand 0.5`](https://crates.io/crates/rand).
+//! * **rayon** -
+//!   Defaults off. Enables parallel algorithms for ``GraphMap`` using
+//!   [`rayon`](https://crates.io/crates/rayon).
 //!
 #![doc(html_root_url = "https://docs.rs/petgraph/0.4/")]
 
@@ -109,6 +115,10 @@ extern crate fixedbitset;
 #[cfg(feature = "graphmap")]
 extern crate indexmap;
 
+#[cfg(feature = "rand")]
+extern crate rand;
+#[cfg(feature = "rayon")]
+extern crate rayon;
 #[cfg(feature = "serde-1")]
 extern crate serde;
 #[cfg(feature = "serde-1")]
@@ -1,11 +1,12 @@
 #![cfg(feature = "graphmap")]
 extern crate petgraph;
 
+use std::cmp;
 use std::collections::HashSet;
 use std::fmt;
 
 use petgraph::prelude::*;
-use petgraph::visit::Walker;
+use petgraph::visit::{IntoEdges, IntoEdgesDirected, Reversed, VisitMap, Visitable, Walker};
 
 use petgraph::algo::dijkstra;
 
@@ -106,6 +107,25 @@ fn remove_directed() {
     assert_eq!(g.neighbors(1).count(), 0);
 }
 
+#[test]
+fn remove_node_with_incoming_edges() {
+    let mut g = DiGraphMap::<_, ()>::from_edges(&[(0, 2), (1, 2), (0, 1)]);
+    assert!(g.remove_node(2));
+    assert_eq!(g.edge_count(), 1);
+    assert_eq!(g.all_edges().count(), 1);
+    assert!(g.contains_edge(0, 1));
+    assert!(!g.contains_edge(0, 2));
+    assert!(!g.contains_edge(1, 2));
+    assert_eq!(
+        g.neighbors_directed(0, Outgoing).collect::<Vec<_>>(),
+        vec![1]
+    );
+    assert_eq!(
+        g.neighbors_directed(1, Incoming).collect::<Vec<_>>(),
+        vec![0]
+    );
+}
+
 #[test]
 fn dfs() {
     let mut gr = UnGraphMap::default();
@@ -335,3 +355,2423 @@ fn self_loops_can_be_removed() {
     assert_eq!(graph.neighbors_directed((), Outgoing).next(), None);
     assert_eq!(graph.neighbors_directed((), Incoming).next(), None);
 }
+
+#[test]
+fn multigraph_parallel_edges() {
+    use petgraph::graphmap::{DiMultiGraphMap, UnMultiGraphMap};
+
+    let mut g = UnMultiGraphMap::new();
+    let red = g.add_edge("a", "b", "red");
+    let blue = g.add_edge("b", "a", "blue");
+    g.add_edge("b", "c", "green");
+    assert_eq!(g.node_count(), 3);
+    assert_eq!(g.edge_count(), 3);
+    assert_eq!(g.edge_multiplicity("a", "b"), 2);
+    assert_eq!(g.neighbors("a").collect::<Vec<_>>(), vec!["b"]);
+
+    let mut lines: Vec<_> = g.edges("b").map(|(_, t, &w)| (t, w)).collect();
+    lines.sort();
+    assert_eq!(lines, vec![("a", "blue"), ("a", "red"), ("c", "green")]);
+    assert_eq!(g.all_edges().count(), 3);
+
+    assert_eq!(g.remove_edge(red), Some("red"));
+    assert_eq!(g.remove_edge(red), None);
+    assert!(g.contains_edge("a", "b"));
+    assert_eq!(g.edge_weight(blue), Some(&"blue"));
+    assert_eq!(g.remove_edge(blue), Some("blue"));
+    assert!(!g.contains_edge("a", "b"));
+    assert_eq!(g.neighbors("a").count(), 0);
+    assert_eq!(g.edge_count(), 1);
+
+    let mut g = DiMultiGraphMap::new();
+    g.add_edge(1, 2, ());
+    g.add_edge(1, 2, ());
+    g.add_edge(2, 1, ());
+    g.add_edge(2, 2, ());
+    g.add_edge(2, 2, ());
+    assert_eq!(g.edges(1).count(), 2);
+    assert_eq!(g.edges(2).count(), 3);
+    let handles: Vec<_> = g.edges_connecting(2, 2).map(|(e, _)| e).collect();
+    assert_eq!(handles.len(), 2);
+    for e in handles {
+        assert_eq!(e.endpoints(), (2, 2));
+        assert_eq!(g.remove_edge(e), Some(()));
+    }
+    assert_eq!(g.neighbors(2).collect::<Vec<_>>(), vec![1]);
+    assert!(g.remove_node(1));
+    assert_eq!(g.edge_count(), 0);
+    assert_eq!(g.all_edges().count(), 0);
+}
+
+#[test]
+fn test_from_graph() {
+    let gr: GraphMap<_, u32, Directed> = GraphMap::from_edges(&[
+        (6, 0, 0),
+        (0, 3, 1),
+        (3, 6, 2),
+        (8, 6, 3),
+        (8, 2, 4),
+        (2, 5, 5),
+        (5, 8, 6),
+        (7, 5, 7),
+        (6, 6, 8),
+    ]);
+    let graph: Graph<_, _, _> = gr.clone().into_graph();
+    let back = DiGraphMap::from_graph(graph);
+    assert_eq!(back.node_count(), gr.node_count());
+    assert_eq!(back.edge_count(), gr.edge_count());
+    for (a, b, w) in gr.all_edges() {
+        assert_eq!(back.edge_weight(a, b), Some(w));
+    }
+
+    // parallel edges are collapsed
+    let mut graph = UnGraph::<_, _>::new_undirected();
+    let a = graph.add_node('a');
+    let b = graph.add_node('b');
+    graph.add_edge(a, b, 1);
+    graph.add_edge(b, a, 2);
+    let gm = UnGraphMap::from_graph(graph);
+    assert_eq!(gm.edge_count(), 1);
+    assert_eq!(gm.edge_weight('a', 'b'), Some(&2));
+}
+
+#[test]
+#[should_panic(expected = "duplicate node weight")]
+fn test_from_graph_duplicate_nodes() {
+    let mut graph = DiGraph::<_, ()>::new();
+    graph.add_node(1);
+    graph.add_node(1);
+    DiGraphMap::from_graph(graph);
+}
+
+#[test]
+fn reverse_directed() {
+    let mut g = DiGraphMap::new();
+    g.add_edge(1, 2, 'a');
+    g.add_edge(2, 3, 'b');
+    g.add_edge(3, 1, 'c');
+    g.add_edge(1, 3, 'd');
+    g.add_edge(2, 2, 'e');
+
+    let orig = g.clone();
+    g.reverse();
+    assert_eq!(g.edge_count(), orig.edge_count());
+    for (a, b, w) in orig.all_edges() {
+        assert_eq!(g.edge_weight(b, a), Some(w));
+    }
+    for n in orig.nodes() {
+        let mut before: Vec<_> = orig.neighbors_directed(n, Incoming).collect();
+        let mut after: Vec<_> = g.neighbors_directed(n, Outgoing).collect();
+        before.sort();
+        after.sort();
+        assert_eq!(before, after);
+    }
+    assert_eq!(
+        g.neighbors_directed(2, Incoming)
+            .filter(|&n| n == 2)
+            .count(),
+        1
+    );
+    assert_eq!(g.edge_weight(2, 2), Some(&'e'));
+
+    let back = g.reversed();
+    for (a, b, w) in orig.all_edges() {
+        assert_eq!(back.edge_weight(a, b), Some(w));
+    }
+
+    let mut u = UnGraphMap::<_, ()>::from_edges(&[(1, 2), (2, 3)]);
+    u.reverse();
+    assert!(u.contains_edge(1, 2));
+    assert_eq!(u.reversed().edge_count(), 2);
+}
+
+#[test]
+fn degree() {
+    let g = DiGraphMap::<_, ()>::from_edges(&[(1, 2), (1, 3), (3, 1), (2, 2)]);
+    assert_eq!(g.out_degree(1), 2);
+    assert_eq!(g.in_degree(1), 1);
+    assert_eq!(g.degree(1), 3);
+    assert_eq!(g.out_degree(2), 1);
+    assert_eq!(g.in_degree(2), 2);
+    assert_eq!(g.degree(2), 3);
+    assert_eq!(g.degree(4), 0);
+    assert_eq!(g.in_degree(4), 0);
+
+    let g = UnGraphMap::<_, ()>::from_edges(&[(1, 2), (1, 3), (2, 2)]);
+    assert_eq!(g.degree(1), 2);
+    assert_eq!(g.degree(2), 2);
+    assert_eq!(g.in_degree(2), 2);
+    assert_eq!(g.out_degree(2), 2);
+    assert_eq!(g.degree(4), 0);
+}
+
+#[test]
+fn directed_self_loops_interleaved() {
+    let mut g = DiGraphMap::new();
+    g.add_edge(1, 1, 10);
+    g.add_edge(1, 2, 12);
+    g.add_edge(2, 1, 21);
+    g.add_edge(2, 2, 20);
+    g.add_edge(2, 3, 23);
+    assert_eq!(g.edge_count(), 5);
+    assert_eq!(g.check_invariants(), Ok(()));
+
+    assert_eq!(g.remove_edge(1, 1), Some(10));
+    assert_eq!(g.remove_edge(1, 1), None);
+    assert!(!g.contains_edge(1, 1));
+    assert_eq!(g.edge_count(), 4);
+    assert_eq!(g.neighbors(1).collect::<Vec<_>>(), vec![2]);
+    assert_eq!(g.check_invariants(), Ok(()));
+
+    g.add_edge(1, 1, 11);
+    g.remove_node(2);
+    assert_eq!(g.edge_count(), 1);
+    assert!(g.contains_edge(1, 1));
+    assert_eq!(g.neighbors(1).collect::<Vec<_>>(), vec![1]);
+    assert_eq!(
+        g.neighbors_directed(1, Incoming).collect::<Vec<_>>(),
+        vec![1]
+    );
+    assert_eq!(g.check_invariants(), Ok(()));
+
+    g.add_edge(3, 3, 33);
+    g.add_edge(1, 3, 13);
+    g.add_edge(3, 1, 31);
+    assert_eq!(g.remove_edge(3, 3), Some(33));
+    assert_eq!(g.edge_count(), 3);
+    assert_eq!(g.check_invariants(), Ok(()));
+
+    g.remove_node(1);
+    assert_eq!(g.edge_count(), 0);
+    assert_eq!(g.remove_edge(3, 1), None);
+    assert_eq!(g.neighbors(3).count(), 0);
+    assert_eq!(g.check_invariants(), Ok(()));
+}
+
+#[test]
+fn graphmap_eq() {
+    let a = UnGraphMap::<_, i32>::from_edges(&[(1, 2, 12), (2, 3, 23), (3, 3, 33)]);
+    let mut b = UnGraphMap::new();
+    b.add_node(3);
+    b.add_edge(3, 3, 33);
+    b.add_edge(3, 2, 23);
+    b.add_edge(2, 1, 12);
+    assert_eq!(a, b);
+
+    b[(1, 2)] = 21;
+    assert_ne!(a, b);
+    b[(1, 2)] = 12;
+    b.add_node(4);
+    assert_ne!(a, b);
+
+    let c = DiGraphMap::<_, ()>::from_edges(&[(1, 2)]);
+    let d = DiGraphMap::<_, ()>::from_edges(&[(2, 1)]);
+    assert_ne!(c, d);
+}
+
+#[test]
+fn subgraph() {
+    let g = DiGraphMap::<_, i32>::from_edges(&[
+        (0, 1, 1),
+        (1, 2, 2),
+        (2, 0, 3),
+        (2, 3, 4),
+        (3, 4, 5),
+        (4, 0, 6),
+        (1, 1, 7),
+    ]);
+    let triangle = g.subgraph(vec![2, 0, 1, 9]);
+    assert_eq!(triangle.node_count(), 3);
+    assert!(!triangle.contains_node(9));
+    assert_eq!(triangle.edge_count(), 4);
+    assert_eq!(triangle[(2, 0)], 3);
+    assert!(!triangle.contains_edge(0, 2));
+    assert_eq!(triangle[(1, 1)], 7);
+    assert_eq!(triangle.check_invariants(), Ok(()));
+
+    let g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3), (3, 0)]);
+    let triangle = g.subgraph(vec![0, 1, 2]);
+    assert_eq!(triangle.edge_count(), 3);
+    assert_eq!(triangle.check_invariants(), Ok(()));
+}
+
+#[test]
+fn extend_from() {
+    let mut g = DiGraphMap::<_, u32>::from_edges(&[(0, 1, 1), (1, 2, 1), (2, 0, 1)]);
+    let disjoint = DiGraphMap::<_, u32>::from_edges(&[(3, 4, 1), (4, 5, 1), (5, 3, 1)]);
+    g.extend_from(&disjoint, |_, _| panic!("no edges should collide"));
+    assert_eq!(g.node_count(), 6);
+    assert_eq!(g.edge_count(), 6);
+    assert_eq!(g.check_invariants(), Ok(()));
+
+    let overlapping = DiGraphMap::<_, u32>::from_edges(&[(0, 1, 5), (1, 0, 5), (1, 6, 5)]);
+    g.add_node(7);
+    g.extend_from(&overlapping, |w, new| *w += new);
+    assert_eq!(g.node_count(), 8);
+    assert_eq!(g.edge_count(), 8);
+    assert_eq!(g[(0, 1)], 6);
+    assert_eq!(g[(1, 0)], 5);
+    assert_eq!(g.check_invariants(), Ok(()));
+
+    g.extend_from(&overlapping, |w, new| *w = new);
+    assert_eq!(g[(0, 1)], 5);
+    assert_eq!(g.edge_count(), 8);
+}
+
+#[test]
+fn shrink_to_fit() {
+    let mut g = DiGraphMap::<u32, ()>::new();
+    g.reserve_nodes(100);
+    g.reserve_edges(10_000);
+    assert!(g.capacity().0 >= 100);
+    assert!(g.capacity().1 >= 10_000);
+    for i in 0..10_000 {
+        g.add_edge(i % 100, i / 100, ());
+    }
+    assert_eq!(g.edge_count(), 10_000);
+    g.retain_edges(|_, a, b| a == 0 && b < 10);
+    g.retain_nodes(|g, n| g.degree(n) > 0);
+    let (nodes, edges) = g.capacity();
+    g.shrink_to_fit();
+    assert!(g.capacity().0 < nodes);
+    assert!(g.capacity().1 < edges);
+    assert!(g.capacity().1 >= g.edge_count());
+    assert_eq!(g.edge_count(), 10);
+    assert_eq!(g.check_invariants(), Ok(()));
+}
+
+#[test]
+fn edges_directed() {
+    let mut g = DiGraphMap::new();
+    g.add_edge(1, 2, 12);
+    g.add_edge(3, 2, 32);
+    g.add_edge(2, 4, 24);
+    g.add_edge(2, 2, 22);
+
+    let mut incoming = g.edges_directed(2, Incoming).collect::<Vec<_>>();
+    incoming.sort();
+    assert_eq!(incoming, vec![(1, 2, &12), (3, 2, &32)]);
+    let mut outgoing = g.edges_directed(2, Outgoing).collect::<Vec<_>>();
+    outgoing.sort();
+    assert_eq!(outgoing, vec![(2, 2, &22), (2, 4, &24)]);
+    assert_eq!(g.edges_directed(5, Incoming).count(), 0);
+
+    // through the visit trait, as used by `Reversed`
+    let mut reversed = Reversed(&g)
+        .edges(2)
+        .map(|e| (e.source(), e.target(), *e.weight()))
+        .collect::<Vec<_>>();
+    reversed.sort();
+    assert_eq!(reversed, vec![(2, 1, 12), (2, 2, 22), (2, 3, 32)]);
+    let mut incoming = IntoEdgesDirected::edges_directed(&g, 2, Incoming)
+        .map(|e| (e.source(), e.target()))
+        .collect::<Vec<_>>();
+    incoming.sort();
+    assert_eq!(incoming, vec![(1, 2), (2, 2), (3, 2)]);
+    assert_eq!(incoming.len(), g.neighbors_directed(2, Incoming).count());
+    let mut outgoing = IntoEdgesDirected::edges_directed(&g, 2, Outgoing)
+        .map(|e| (e.source(), e.target()))
+        .collect::<Vec<_>>();
+    outgoing.sort();
+    assert_eq!(outgoing, vec![(2, 2), (2, 4)]);
+
+    let g = UnGraphMap::<_, i32>::from_edges(&[(1, 2, 12), (3, 2, 32), (2, 2, 22)]);
+    for &dir in &[Outgoing, Incoming] {
+        let mut edges = g.edges_directed(2, dir).collect::<Vec<_>>();
+        edges.sort();
+        let mut expected = g.edges(2).collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!(edges, expected);
+    }
+}
+
+#[test]
+fn isolated_nodes() {
+    let mut g = UnGraphMap::<_, ()>::from_nodes(vec![1, 2, 3, 2]);
+    g.add_nodes_from(vec![3, 4, 5]);
+    assert_eq!(g.node_count(), 5);
+    assert_eq!(g.edge_count(), 0);
+    for n in 1..6 {
+        assert!(g.contains_node(n));
+        assert_eq!(g.neighbors(n).count(), 0);
+    }
+}
+
+#[test]
+fn to_graph() {
+    let mut gm = DiGraphMap::new();
+    gm.add_edge(3, 1, 31.);
+    gm.add_edge(1, 2, 12.);
+    gm.add_edge(2, 2, 22.);
+    gm.add_node(7);
+
+    let (graph, indices) = gm.to_graph::<u16>();
+    assert_eq!(graph.node_count(), 4);
+    assert_eq!(graph.edge_count(), 3);
+    for (&n, &ix) in &indices {
+        assert_eq!(graph[ix], n);
+    }
+    for (a, b, &w) in gm.all_edges() {
+        let e = graph.find_edge(indices[&a], indices[&b]).unwrap();
+        assert_eq!(graph[e], w);
+    }
+
+    // the GraphMap is still usable
+    gm.add_edge(7, 3, 73.);
+    assert_eq!(gm.edge_count(), 4);
+    assert_eq!(graph.edge_count(), 3);
+}
+
+#[test]
+fn relabel_nodes() {
+    let g = DiGraphMap::<u32, i32>::from_edges(&[(100, 7, 1), (7, 100, 2), (7, 7, 3), (55, 7, 4)]);
+    let mut order = g.nodes().collect::<Vec<_>>();
+    order.sort();
+    let h = g.relabel_nodes(|n| order.binary_search(&n).unwrap());
+    assert_eq!(h.node_count(), 3);
+    assert_eq!(h.edge_count(), 4);
+    assert_eq!(h[(2, 0)], 1);
+    assert_eq!(h[(0, 2)], 2);
+    assert_eq!(h[(0, 0)], 3);
+    assert_eq!(h[(1, 0)], 4);
+    assert!(!h.contains_edge(0, 1));
+    assert_eq!(h.check_invariants(), Ok(()));
+}
+
+#[test]
+#[should_panic(expected = "same label")]
+fn relabel_nodes_collision() {
+    let g = UnGraphMap::<u32, ()>::from_edges(&[(1, 2), (2, 3)]);
+    g.relabel_nodes(|n| n / 2);
+}
+
+#[test]
+fn to_adjacency_matrix() {
+    let g = UnGraphMap::<_, u32>::from_edges(&[(3, 1, 31), (1, 2, 12), (2, 2, 22)]);
+    let (nodes, matrix) = g.to_adjacency_matrix();
+    assert_eq!(nodes, g.nodes().collect::<Vec<_>>());
+    let n = nodes.len();
+    assert_eq!(matrix.len(), n * n);
+    for (i, &a) in nodes.iter().enumerate() {
+        for (j, &b) in nodes.iter().enumerate() {
+            assert_eq!(matrix[i * n + j], matrix[j * n + i]);
+            assert_eq!(matrix[i * n + j], g.edge_weight(a, b).cloned().unwrap_or(0));
+        }
+    }
+
+    let g = DiGraphMap::<_, u32>::from_edges(&[(3, 1, 31), (1, 2, 12), (2, 2, 22)]);
+    let (nodes, matrix) = g.to_adjacency_matrix();
+    assert_eq!(nodes, vec![3, 1, 2]);
+    assert_eq!(matrix, vec![0, 31, 0, 0, 0, 12, 0, 0, 22]);
+}
+
+#[test]
+fn undirected_lookup() {
+    let mut g = DiGraphMap::new();
+    g.add_edge(1, 2, 12);
+    g.add_edge(3, 2, 32);
+    g.add_edge(2, 3, 23);
+    assert!(g.contains_edge_undirected(2, 1));
+    assert!(g.contains_edge_undirected(1, 2));
+    assert!(!g.contains_edge_undirected(1, 3));
+    assert_eq!(g.edge_weight_undirected(2, 1), Some(&12));
+    assert_eq!(g.edge_weight_undirected(2, 3), Some(&23));
+    assert_eq!(g.edge_weight_undirected(3, 2), Some(&32));
+    assert_eq!(g.edge_weight_undirected(1, 3), None);
+
+    let g = UnGraphMap::<_, i32>::from_edges(&[(1, 2, 12)]);
+    assert!(g.contains_edge_undirected(2, 1));
+    assert_eq!(g.edge_weight_undirected(2, 1), Some(&12));
+}
+
+#[test]
+fn remove_node_with_edges() {
+    let mut g = UnGraphMap::new();
+    g.add_edge(1, 2, 12);
+    g.add_edge(2, 2, 22);
+    g.add_edge(2, 3, 23);
+    g.add_edge(3, 1, 31);
+    let mut edges = g.remove_node_with_edges(2).unwrap();
+    edges.sort();
+    assert_eq!(edges, vec![(1, 12), (2, 22), (3, 23)]);
+    assert_eq!(g.edge_count(), 1);
+    assert_eq!(g.remove_node_with_edges(2), None);
+    assert_eq!(g.check_invariants(), Ok(()));
+
+    let mut g = DiGraphMap::new();
+    g.add_edge(1, 2, 12);
+    g.add_edge(2, 1, 21);
+    g.add_edge(1, 1, 11);
+    let mut edges = g.remove_node_with_edges(1).unwrap();
+    edges.sort();
+    assert_eq!(edges, vec![(1, 11), (2, 12), (2, 21)]);
+    assert_eq!(g.edge_count(), 0);
+    assert_eq!(g.remove_node_with_edges(3), None);
+    assert_eq!(g.check_invariants(), Ok(()));
+}
+
+#[test]
+fn bfs_directed() {
+    let gr = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (0, 2), (1, 3), (2, 3), (4, 0), (3, 5)]);
+    let order = Bfs::new(&gr, 0).iter(&gr).collect::<Vec<_>>();
+    assert_eq!(order.len(), 5);
+    assert_eq!(order[0], 0);
+    assert_eq!(
+        order[1..3].iter().cloned().collect::<HashSet<_>>(),
+        [1, 2].iter().cloned().collect()
+    );
+    assert_eq!(&order[3..], &[3, 5]);
+    // 4 only has an outgoing edge towards the root
+    assert!(!order.contains(&4));
+
+    let mut bfs = Bfs::new(&gr, 5);
+    assert_eq!(bfs.next(&gr), Some(5));
+    assert_eq!(bfs.next(&gr), None);
+
+    let mut map = gr.visit_map();
+    assert!(map.visit(3));
+    assert!(!map.visit(3));
+    assert!(map.is_visited(&3));
+    gr.reset_map(&mut map);
+    assert!(!map.is_visited(&3));
+}
+
+#[test]
+fn into_nodes_edges() {
+    let mut g = DiGraphMap::new();
+    g.add_node(5);
+    g.add_edge(2, 1, "a".to_string());
+    g.add_edge(1, 1, "b".to_string());
+    let nodes = g.nodes().collect::<Vec<_>>();
+    let edges = g
+        .all_edges()
+        .map(|(a, b, w)| (a, b, w.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(g.into_nodes_edges(), (nodes, edges));
+}
+
+#[test]
+fn adjacencies() {
+    let mut g = DiGraphMap::<_, ()>::new();
+    g.add_edge(1, 2, ());
+    g.add_edge(3, 1, ());
+    g.add_edge(1, 1, ());
+    g.add_node(4);
+    let adjacencies = g.adjacencies(1).unwrap();
+    assert_eq!(adjacencies.len(), 3);
+    assert_eq!(
+        adjacencies.rev().collect::<Vec<_>>(),
+        vec![(1, Outgoing), (3, Incoming), (2, Outgoing)]
+    );
+    assert_eq!(
+        g.adjacencies(2).unwrap().collect::<Vec<_>>(),
+        vec![(1, Incoming)]
+    );
+    assert_eq!(g.adjacencies(4).unwrap().len(), 0);
+    assert!(g.adjacencies(5).is_none());
+}
+
+#[test]
+fn clear_edges() {
+    let mut g = DiGraphMap::<_, ()>::from_edges(&[(1, 2), (2, 3), (3, 3), (3, 1)]);
+    g.add_node(4);
+    g.clear_edges();
+    assert_eq!(g.node_count(), 4);
+    assert_eq!(g.edge_count(), 0);
+    for n in g.nodes() {
+        assert_eq!(g.adjacencies(n).unwrap().len(), 0);
+    }
+    g.add_edge(4, 1, ());
+    g.add_edge(2, 2, ());
+    assert_eq!(g.edge_count(), 2);
+    assert_eq!(g.neighbors(3).count(), 0);
+    assert_eq!(g.neighbors(1).count(), 0);
+    assert_eq!(g.neighbors(4).collect::<Vec<_>>(), vec![1]);
+    assert_eq!(g.check_invariants(), Ok(()));
+}
+
+#[test]
+fn from_edges_with() {
+    let mut edges = Vec::new();
+    for x in 0..4i32 {
+        for y in 0..4i32 {
+            if x < 3 {
+                edges.push(((x + 1, y), (x, y)));
+            }
+            if y < 3 {
+                edges.push(((x, y), (x, y + 1)));
+            }
+        }
+    }
+    let grid = UnGraphMap::from_edges_with(edges, |a: (i32, i32), b: (i32, i32)| {
+        assert!(a < b);
+        (a.0 - b.0).abs() + (a.1 - b.1).abs()
+    });
+    assert_eq!(grid.node_count(), 16);
+    assert_eq!(grid.edge_count(), 24);
+    assert!(grid.all_edges().all(|(_, _, &w)| w == 1));
+
+    let g = DiGraphMap::from_edges_with(vec![(2, 1), (1, 2)], |a: i32, b: i32| a - b);
+    assert_eq!(g[(2, 1)], 1);
+    assert_eq!(g[(1, 2)], -1);
+}
+
+#[test]
+fn tarjan_scc() {
+    // a 2-cycle with a tail leading into it, and one leading out of it
+    let g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 1), (2, 3), (3, 3)]);
+    let mut sccs = g.tarjan_scc();
+    for scc in &mut sccs {
+        scc.sort();
+    }
+    assert_eq!(sccs, vec![vec![3], vec![1, 2], vec![0]]);
+
+    let g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (3, 4)]);
+    let mut sccs = g.tarjan_scc();
+    for scc in &mut sccs {
+        scc.sort();
+    }
+    sccs.sort();
+    assert_eq!(sccs, vec![vec![0, 1, 2], vec![3, 4]]);
+}
+
+#[test]
+fn component_labels() {
+    let mut g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)]);
+    g.add_node(6);
+    assert_eq!(g.connected_components(), 3);
+    let labels = g.component_labels();
+    assert_eq!(labels.len(), 7);
+    for (a, b, _) in g.all_edges() {
+        assert_eq!(labels[&a], labels[&b]);
+    }
+    assert_eq!(
+        g.nodes().map(|n| labels[&n]).collect::<Vec<_>>(),
+        vec![0, 0, 0, 1, 1, 1, 2]
+    );
+}
+
+#[test]
+fn neighbors_tagged() {
+    let g = DiGraphMap::<_, ()>::from_edges(&[(1, 2), (3, 1), (2, 1), (1, 1)]);
+    let mut tagged = g.neighbors_tagged(1).collect::<Vec<_>>();
+    tagged.sort();
+    assert_eq!(
+        tagged,
+        vec![(1, Outgoing), (2, Outgoing), (2, Incoming), (3, Incoming)]
+    );
+    for n in g.nodes() {
+        let mut outgoing = g
+            .neighbors_tagged(n)
+            .filter(|&(_, dir)| dir == Outgoing)
+            .map(|(m, _)| m)
+            .collect::<Vec<_>>();
+        outgoing.sort();
+        let mut expected = g.neighbors(n).collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!(outgoing, expected);
+    }
+    assert_eq!(g.neighbors_tagged(4).count(), 0);
+}
+
+#[test]
+fn remove_edges_from() {
+    let mut g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 0), (1, 2), (2, 2), (2, 3)]);
+    let removed = g.remove_edges_from(vec![(0, 1), (0, 1), (2, 2), (3, 2), (4, 5), (2, 3)]);
+    assert_eq!(removed, 3);
+    assert_eq!(g.edge_count(), 2);
+    assert!(g.contains_edge(1, 0) && g.contains_edge(1, 2));
+    assert_eq!(g.node_count(), 4);
+    assert_eq!(g.check_invariants(), Ok(()));
+
+    let mut g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 2)]);
+    assert_eq!(g.remove_edges_from(vec![(2, 1), (2, 2), (1, 2)]), 2);
+    assert_eq!(g.edge_count(), 1);
+    assert_eq!(g.check_invariants(), Ok(()));
+}
+
+#[test]
+fn graph_node_order() {
+    let mut gm = UnGraphMap::new();
+    for &n in &[17, 3, 42, 8, 1, 99, 23] {
+        gm.add_node(n);
+    }
+    for &(a, b) in &[(42, 1), (3, 99), (23, 17), (8, 8), (99, 42)] {
+        gm.add_edge(a, b, a * 100 + b);
+    }
+    gm.remove_node(3);
+
+    let (graph, indices) = gm.to_graph::<u32>();
+    assert_eq!(indices.len(), gm.node_count());
+    for (&n, &ix) in &indices {
+        assert_eq!(graph[ix], n);
+    }
+    let order = gm.nodes().collect::<Vec<_>>();
+    let graph = gm.clone().into_graph::<u32>();
+    for (i, ix) in graph.node_indices().enumerate() {
+        assert_eq!(graph[ix], order[i]);
+        assert_eq!(indices[&order[i]], ix);
+    }
+    for e in graph.edge_references() {
+        let (a, b) = (graph[e.source()], graph[e.target()]);
+        assert_eq!(gm.edge_weight(a, b), Some(e.weight()));
+    }
+}
+
+#[test]
+fn has_path_connecting() {
+    let mut g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3), (4, 3)]);
+    g.add_node(5);
+    assert!(g.has_path_connecting(0, 3));
+    assert!(g.has_path_connecting(5, 5));
+    assert!(!g.has_path_connecting(3, 0));
+    assert!(!g.has_path_connecting(0, 4));
+    assert!(!g.has_path_connecting(0, 6));
+
+    let mut visited = HashSet::new();
+    assert!(!g.has_path_connecting_with_visited(1, 4, &mut visited));
+    assert_eq!(visited, [0, 1, 2, 3].iter().cloned().collect());
+    assert!(g.has_path_connecting_with_visited(4, 3, &mut visited));
+
+    let g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (2, 1)]);
+    assert!(g.has_path_connecting(2, 0));
+}
+
+#[test]
+fn dijkstra_keys() {
+    let mut g = DiGraphMap::<&str, u32>::new();
+    g.add_edge("a", "b", 7);
+    g.add_edge("a", "c", 9);
+    g.add_edge("a", "f", 14);
+    g.add_edge("b", "c", 10);
+    g.add_edge("b", "d", 15);
+    g.add_edge("c", "d", 11);
+    g.add_edge("c", "f", 2);
+    g.add_edge("e", "f", 9);
+    g.add_edge("f", "d", 3);
+    g.add_node("g");
+
+    let costs = g.dijkstra("a", |_, _, &w| w);
+    let expected = [("a", 0), ("b", 7), ("c", 9), ("d", 14), ("f", 11)];
+    assert_eq!(costs.len(), expected.len());
+    for &(n, cost) in &expected {
+        assert_eq!(costs[n], cost);
+    }
+    assert!(!costs.contains_key("e"));
+    assert!(!costs.contains_key("g"));
+
+    let costs = g.dijkstra("e", |_, _, _| 1);
+    assert_eq!(
+        costs.into_iter().collect::<Vec<_>>(),
+        vec![("e", 0), ("f", 1), ("d", 2)]
+    );
+    assert!(g.dijkstra("x", |_, _, &w| w).is_empty());
+}
+
+#[test]
+fn degree_sequence_density() {
+    let k5 = UnGraphMap::<_, ()>::from_nodes(0..5).complement(());
+    assert_eq!(k5.edge_count(), 10);
+    assert_eq!(k5.degree_sequence(), vec![4; 5]);
+    assert!((k5.density() - 1.).abs() < 1e-9);
+
+    let k5 = DiGraphMap::<_, ()>::from_nodes(0..5).complement(());
+    assert_eq!(k5.degree_sequence(), vec![8; 5]);
+    assert!((k5.density() - 1.).abs() < 1e-9);
+
+    let g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (0, 2), (1, 1)]);
+    assert_eq!(g.degree_sequence(), vec![3, 2, 1]);
+    assert!((g.density() - 0.5).abs() < 1e-9);
+    assert_eq!(DiGraphMap::<i32, ()>::from_nodes(0..1).density(), 0.);
+}
+
+#[test]
+fn is_cyclic() {
+    let mut g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (0, 2), (1, 3), (2, 3), (3, 4)]);
+    assert!(!g.is_cyclic());
+    g.add_edge(4, 1, ());
+    assert!(g.is_cyclic());
+
+    let mut g = DiGraphMap::<_, ()>::new();
+    g.add_edge(0, 0, ());
+    assert!(g.is_cyclic());
+
+    let mut g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (3, 2)]);
+    assert!(!g.is_cyclic());
+    g.add_edge(3, 3, ());
+    assert!(g.is_cyclic());
+    g.remove_edge(3, 3);
+    g.add_edge(0, 3, ());
+    assert!(g.is_cyclic());
+}
+
+#[test]
+fn toposort_keys() {
+    let mut g = DiGraphMap::<_, ()>::from_edges(&[
+        ("app", "http"),
+        ("app", "log"),
+        ("http", "net"),
+        ("http", "log"),
+        ("net", "libc"),
+        ("log", "libc"),
+    ]);
+    g.add_node("docs");
+    let order = g.toposort().unwrap();
+    assert_eq!(order.len(), g.node_count());
+    let position = |n| order.iter().position(|&m| m == n).unwrap();
+    for (a, b, _) in g.all_edges() {
+        assert!(position(a) < position(b));
+    }
+
+    // a cycle downstream of `app`; the error must be on the cycle
+    g.add_edge("libc", "http", ());
+    let node = g.toposort().unwrap_err();
+    assert!(["http", "net", "log", "libc"].contains(&node));
+    g.remove_edge("libc", "http");
+    g.add_edge("docs", "docs", ());
+    assert_eq!(g.toposort(), Err("docs"));
+}
+
+#[test]
+fn borrowed_string_nodes() {
+    let names = ["a", "b", "c", "d"]
+        .iter()
+        .map(|s| s.repeat(3))
+        .collect::<Vec<String>>();
+    let mut g = UnGraphMap::<&str, ()>::new();
+    g.add_edge(&names[0], &names[1], ());
+    g.add_edge(&names[1], &names[2], ());
+    g.add_edge(&names[2], &names[0], ());
+    g.add_edge(&names[3], &names[3], ());
+    assert_eq!(g.node_count(), 4);
+    let mut neighbors = g.neighbors("aaa").collect::<Vec<_>>();
+    neighbors.sort();
+    assert_eq!(neighbors, vec!["bbb", "ccc"]);
+
+    assert!(g.remove_edge("bbb", "aaa").is_some());
+    assert!(g.remove_node("ccc"));
+    assert_eq!(g.edge_count(), 1);
+    assert_eq!(g.neighbors("aaa").count(), 0);
+    assert_eq!(g.neighbors("ddd").collect::<Vec<_>>(), vec!["ddd"]);
+    assert_eq!(g.check_invariants(), Ok(()));
+}
+
+#[test]
+fn edge_entry() {
+    let pairs = [(1, 2), (2, 3), (1, 2), (3, 3), (1, 2), (3, 3), (2, 1)];
+    let mut g = DiGraphMap::<_, u32>::new();
+    for &(a, b) in &pairs {
+        *g.edge_entry(a, b).or_insert(0) += 1;
+    }
+    assert_eq!(g.edge_count(), 4);
+    assert_eq!(g[(1, 2)], 3);
+    assert_eq!(g[(2, 1)], 1);
+    assert_eq!(g[(3, 3)], 2);
+    assert_eq!(g.check_invariants(), Ok(()));
+
+    g.edge_entry(2, 3).and_modify(|w| *w *= 10).or_insert(0);
+    assert_eq!(g[(2, 3)], 10);
+    let w = g
+        .edge_entry(3, 4)
+        .and_modify(|_| panic!())
+        .or_insert_with(|| 5);
+    assert_eq!(*w, 5);
+    assert_eq!(g.edge_entry(4, 3).endpoints(), (4, 3));
+    assert_eq!(g.edge_count(), 5);
+    assert_eq!(g.check_invariants(), Ok(()));
+}
+
+#[test]
+fn node_adjacencies() {
+    fn edge_set(g: &DiGraphMap<i32, ()>) -> HashSet<(i32, i32)> {
+        let mut edges = HashSet::new();
+        for (a, adjacencies) in g.node_adjacencies() {
+            for (b, dir) in adjacencies {
+                match dir {
+                    Outgoing => edges.insert((a, b)),
+                    Incoming => edges.insert((b, a)),
+                };
+            }
+        }
+        edges
+    }
+    let mut g = DiGraphMap::from_edges(&[(0, 1), (1, 0), (1, 2), (2, 2), (3, 1)]);
+    g.add_node(4);
+    assert_eq!(g.node_adjacencies().len(), 5);
+    assert_eq!(
+        edge_set(&g),
+        g.all_edges()
+            .map(|(a, b, _)| (a, b))
+            .collect::<HashSet<_>>()
+    );
+}
+
+#[test]
+fn add_edge_unweighted() {
+    let mut path = UnGraphMap::<_, ()>::new();
+    for i in 0..9 {
+        assert!(path.add_edge_unweighted(i, i + 1));
+    }
+    assert!(!path.add_edge_unweighted(5, 4));
+    assert_eq!(path.node_count(), 10);
+    assert_eq!(path.edge_count(), 9);
+
+    let mut g = DiGraphMap::<_, u32>::new();
+    g.add_edge(0, 1, 7);
+    assert!(!g.add_edge_unweighted(0, 1));
+    assert!(g.add_edge_unweighted(1, 0));
+    assert_eq!(g[(0, 1)], 7);
+    assert_eq!(g[(1, 0)], 0);
+}
+
+#[test]
+fn sort_nodes_edges() {
+    let mut g = UnGraphMap::<_, ()>::new();
+    for &(a, b) in &[(5, 1), (3, 4), (9, 2), (1, 3), (8, 8), (2, 6), (7, 5)] {
+        g.add_edge(a, b, ());
+    }
+    g.remove_node(3);
+    g.remove_node(9);
+    g.remove_edge(5, 1);
+    g.add_node(0);
+    g.sort_nodes();
+    assert_eq!(g.nodes().collect::<Vec<_>>(), vec![0, 1, 2, 4, 5, 6, 7, 8]);
+    g.sort_edges();
+    assert_eq!(
+        g.all_edges().map(|(a, b, _)| (a, b)).collect::<Vec<_>>(),
+        vec![(2, 6), (5, 7), (8, 8)]
+    );
+    assert_eq!(g.check_invariants(), Ok(()));
+}
+
+#[test]
+fn to_dot() {
+    let mut g = DiGraphMap::new();
+    g.add_edge("a", "b", 1.5);
+    g.add_edge("b", "c", 2.);
+    g.add_edge("c", "a", 0.25);
+    let dot = g.to_dot();
+    assert!(dot.starts_with("digraph {\n"));
+    assert!(dot.contains("    \"a\" -> \"b\" [ label = \"1.5\" ]\n"));
+    assert!(dot.contains("    \"b\" -> \"c\" [ label = \"2\" ]\n"));
+    assert!(dot.contains("    \"c\" -> \"a\" [ label = \"0.25\" ]\n"));
+
+    let mut g = UnGraphMap::new();
+    g.add_edge("say \"hi\"", "x", 'w');
+    g.add_node("lonely");
+    let dot = g.to_dot();
+    assert!(dot.starts_with("graph {\n"));
+    assert!(dot.contains("    \"lonely\"\n"));
+    assert!(dot.contains("\"say \\\"hi\\\"\" -- \"x\" [ label = \"w\" ]"));
+}
+
+#[test]
+fn edge_endpoints() {
+    let mut g = UnGraphMap::new();
+    g.add_edge(5, 2, "x");
+    assert_eq!(g.edge_endpoints(5, 2), Some((2, 5)));
+    assert_eq!(g.edge_endpoints(2, 5), Some((2, 5)));
+    assert_eq!(g.edge_endpoints(2, 2), None);
+    assert_eq!(g[g.edge_endpoints(5, 2).unwrap()], "x");
+
+    let mut g = DiGraphMap::new();
+    g.add_edge(5, 2, "x");
+    assert_eq!(g.edge_endpoints(5, 2), Some((5, 2)));
+    assert_eq!(g.edge_endpoints(2, 5), None);
+}
+
+#[test]
+fn from_edges_capacity() {
+    let edges = (0..1000u32)
+        .map(|i| (i % 37, (i * 7) % 101, i))
+        .collect::<Vec<_>>();
+    let g = DiGraphMap::<_, u32>::from_edges(&edges);
+    assert!(g.capacity().0 >= g.node_count());
+    let mut h = DiGraphMap::new();
+    for &(a, b, w) in &edges {
+        h.add_edge(a, b, w);
+    }
+    assert_eq!(g, h);
+    assert!(g.nodes().eq(h.nodes()));
+    assert!(g.all_edges().eq(h.all_edges()));
+}
+
+#[test]
+fn filter_edges() {
+    let g = DiGraphMap::<_, i32>::from_edges(&[(0, 1, 3), (1, 2, -2), (2, 0, -1), (2, 2, 5)]);
+    let h = g.filter_edges(|_, _, &w| w >= 0);
+    assert_eq!(h.node_count(), 3);
+    assert_eq!(h.edge_count(), 2);
+    assert!(h.contains_edge(0, 1) && h.contains_edge(2, 2));
+    assert_eq!(h.neighbors(1).count(), 0);
+    assert_eq!(h.check_invariants(), Ok(()));
+    // the original is unchanged
+    assert_eq!(g.edge_count(), 4);
+    assert_eq!(g[(1, 2)], -2);
+}
+
+#[test]
+fn extend_with() {
+    let edges = [(0, 1, 1), (1, 2, 2), (2, 2, 3), (2, 0, 4)];
+    let mut g = DiGraphMap::<_, u32>::new();
+    g.extend_with(&edges, |w, new| *w += new);
+    g.extend_with(&edges, |w, new| *w += new);
+    assert_eq!(g.edge_count(), 4);
+    for &(a, b, w) in &edges {
+        assert_eq!(g[(a, b)], 2 * w);
+    }
+    assert_eq!(g.check_invariants(), Ok(()));
+
+    let mut g = UnGraphMap::<_, Vec<char>>::new();
+    g.extend_with(vec![(0, 1, vec!['a']), (1, 0, vec!['b'])], |w, new| {
+        w.extend(new)
+    });
+    assert_eq!(g[(0, 1)], vec!['a', 'b']);
+}
+
+#[test]
+fn line_graph() {
+    let path = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (2, 1), (2, 3)]);
+    let l = path.line_graph();
+    assert_eq!(l.node_count(), 3);
+    assert_eq!(l.edge_count(), 2);
+    assert!(l.contains_edge((0, 1), (1, 2)));
+    assert!(l.contains_edge((1, 2), (2, 3)));
+    assert!(!l.contains_edge((0, 1), (2, 3)));
+
+    // edges sharing an endpoint are adjacent whatever their direction
+    let g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 0), (2, 1), (3, 3)]);
+    let l = g.line_graph();
+    assert_eq!(l.node_count(), 4);
+    assert_eq!(l.edge_count(), 3);
+    assert!(l.contains_edge((0, 1), (1, 0)));
+    assert!(l.contains_edge((2, 1), (1, 0)));
+    assert_eq!(l.neighbors((3, 3)).count(), 0);
+}
+
+#[test]
+fn k_core() {
+    let mut g = UnGraphMap::new();
+    for &(a, b) in &[(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3), (3, 4)] {
+        g.add_edge(a, b, a * 10 + b);
+    }
+    let core = g.k_core(3);
+    assert_eq!(core.check_invariants(), Ok(()));
+    assert_eq!(core.node_count(), 4);
+    assert_eq!(core.edge_count(), 6);
+    assert!(!core.contains_node(4));
+    assert_eq!(core.edge_weight(1, 3), Some(&13));
+    assert_eq!(g.k_core(0), g);
+
+    let core = g.k_core(4);
+    assert_eq!(core.node_count(), 0);
+    assert_eq!(core.edge_count(), 0);
+}
+
+#[test]
+fn minimum_spanning_tree() {
+    let g = UnGraphMap::<_, _>::from_edges(&[
+        ('a', 'b', 7),
+        ('a', 'd', 5),
+        ('b', 'c', 8),
+        ('b', 'd', 9),
+        ('b', 'e', 7),
+        ('c', 'e', 5),
+        ('d', 'e', 15),
+        ('d', 'f', 6),
+        ('e', 'f', 8),
+        ('e', 'g', 9),
+        ('f', 'g', 11),
+        ('h', 'i', 1),
+        ('h', 'h', 0),
+    ]);
+    let mst = g.minimum_spanning_tree();
+    assert_eq!(mst.check_invariants(), Ok(()));
+    assert_eq!(mst.node_count(), g.node_count());
+    let mut edges = mst
+        .all_edges()
+        .map(|(a, b, &w)| (a, b, w))
+        .collect::<Vec<_>>();
+    edges.sort();
+    assert_eq!(
+        edges,
+        vec![
+            ('a', 'b', 7),
+            ('a', 'd', 5),
+            ('b', 'e', 7),
+            ('c', 'e', 5),
+            ('d', 'f', 6),
+            ('e', 'g', 9),
+            ('h', 'i', 1),
+        ]
+    );
+    assert_eq!(mst.all_edges().map(|(_, _, &w)| w).sum::<i32>(), 40);
+}
+
+#[test]
+fn split_off() {
+    let mut g = DiGraphMap::new();
+    for &(a, b) in &[
+        (0, 2),
+        (2, 0),
+        (1, 3),
+        (3, 5),
+        (0, 1),
+        (4, 3),
+        (4, 4),
+        (5, 5),
+    ] {
+        g.add_edge(a, b, (a, b));
+    }
+    g.add_node(6);
+    let odd = g.split_off(|n| n % 2 == 1);
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(odd.check_invariants(), Ok(()));
+    assert_eq!(g.nodes().collect::<Vec<_>>(), vec![0, 2, 4, 6]);
+    assert_eq!(odd.nodes().collect::<Vec<_>>(), vec![1, 3, 5]);
+
+    // the cross edges (0, 1) and (4, 3) are gone from both graphs
+    let edges = |g: &DiGraphMap<_, _>| g.all_edges().map(|(_, _, &w)| w).collect::<Vec<_>>();
+    assert_eq!(edges(&g), vec![(0, 2), (2, 0), (4, 4)]);
+    assert_eq!(edges(&odd), vec![(1, 3), (3, 5), (5, 5)]);
+    assert!(!g.contains_edge(0, 1));
+    assert_eq!(g.neighbors(0).collect::<Vec<_>>(), vec![2]);
+    assert_eq!(
+        odd.neighbors_directed(3, Incoming).collect::<Vec<_>>(),
+        vec![1]
+    );
+
+    let rest = g.split_off(|_| false);
+    assert_eq!(rest.node_count(), 0);
+    assert_eq!(g.node_count(), 4);
+}
+
+#[test]
+fn from_sorted_edges() {
+    let edges = vec![(0, 1, 1.), (0, 3, 2.), (1, 1, 3.), (2, 0, 4.), (2, 3, 5.)];
+    let gr = DiGraphMap::from_sorted_edges(edges.clone()).unwrap();
+    assert_eq!(gr.check_invariants(), Ok(()));
+    assert_eq!(gr, DiGraphMap::from_edges(edges));
+
+    let edges = vec![(1, 0, 1.), (0, 3, 2.), (3, 1, 3.), (2, 3, 4.)];
+    let gr = UnGraphMap::from_sorted_edges(edges.clone()).unwrap();
+    assert_eq!(gr.check_invariants(), Ok(()));
+    assert_eq!(gr, UnGraphMap::from_edges(edges));
+
+    // out of order
+    assert!(DiGraphMap::from_sorted_edges(vec![(0, 2, ()), (0, 1, ())]).is_err());
+    assert!(DiGraphMap::from_sorted_edges(vec![(1, 0, ()), (0, 1, ())]).is_err());
+    let err = UnGraphMap::from_sorted_edges(vec![(1, 3, ()), (2, 0, ())]).unwrap_err();
+    assert_eq!(err.first_error(), (2, 0));
+    // repeated
+    assert!(DiGraphMap::from_sorted_edges(vec![(0, 1, ()), (0, 1, ())]).is_err());
+    assert!(UnGraphMap::from_sorted_edges(vec![(0, 1, ()), (1, 0, ())]).is_err());
+    assert!(UnGraphMap::<u8, ()>::from_sorted_edges(vec![]).is_ok());
+}
+
+#[test]
+fn transitive_closure() {
+    let g = DiGraphMap::<_, ()>::from_edges(&[('a', 'b'), ('b', 'c')]);
+    let closure = g.transitive_closure();
+    assert_eq!(closure.check_invariants(), Ok(()));
+    let mut edges = closure
+        .all_edges()
+        .map(|(a, b, _)| (a, b))
+        .collect::<Vec<_>>();
+    edges.sort();
+    assert_eq!(edges, vec![('a', 'b'), ('a', 'c'), ('b', 'c')]);
+
+    // self loops only for nodes on a cycle
+    let mut g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 1), (3, 3)]);
+    g.add_node(4);
+    let closure = g.transitive_closure();
+    assert_eq!(closure.node_count(), 5);
+    assert!(!closure.contains_edge(0, 0));
+    assert!(closure.contains_edge(0, 2));
+    assert!(closure.contains_edge(1, 1));
+    assert!(closure.contains_edge(2, 2));
+    assert!(closure.contains_edge(3, 3));
+    assert!(!closure.contains_edge(2, 0));
+    assert_eq!(closure.edge_count(), 7);
+}
+
+#[test]
+fn reciprocal_edges() {
+    let mut g =
+        DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 0), (2, 1), (1, 2), (0, 2), (3, 0), (2, 3)]);
+    assert_eq!(g.reciprocal_edges(), 2);
+    g.add_edge(3, 3, ());
+    assert_eq!(g.reciprocal_edges(), 2);
+    g.add_edge(0, 3, ());
+    assert_eq!(g.reciprocal_edges(), 3);
+    g.remove_edge(1, 0);
+    assert_eq!(g.reciprocal_edges(), 2);
+}
+
+#[test]
+fn contract_nodes() {
+    let mut g = UnGraphMap::new();
+    g.add_edge('a', 'b', 1);
+    g.add_edge('b', 'c', 2);
+    g.add_edge('c', 'a', 4);
+    g.contract_nodes('a', 'b', |w, other| *w += other);
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.node_count(), 2);
+    assert_eq!(g.edge_count(), 1);
+    assert_eq!(g.edge_weight('a', 'c'), Some(&6));
+
+    let mut g = DiGraphMap::new();
+    g.add_edge(0, 1, 1);
+    g.add_edge(1, 0, 2);
+    g.add_edge(1, 2, 3);
+    g.add_edge(3, 1, 4);
+    g.add_edge(0, 2, 5);
+    g.add_edge(2, 0, 6);
+    g.add_edge(1, 1, 7);
+    g.contract_nodes(0, 1, |w, other| *w += other);
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert!(!g.contains_node(1));
+    assert_eq!(g.edge_weight(0, 2), Some(&8));
+    assert_eq!(g.edge_weight(2, 0), Some(&6));
+    assert_eq!(g.edge_weight(3, 0), Some(&4));
+    // the edges between 0 and 1 and the self loop of 1 are dropped
+    assert_eq!(g.edge_count(), 3);
+
+    g.add_edge(0, 0, 10);
+    g.add_edge(4, 4, 20);
+    g.contract_nodes(0, 4, |w, other| *w += other);
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.edge_weight(0, 0), Some(&30));
+
+    // contracting into a new node relabels it
+    g.contract_nodes(5, 3, |_, _| panic!());
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.edge_weight(5, 0), Some(&4));
+    g.contract_nodes(5, 5, |_, _| panic!());
+    g.contract_nodes(5, 6, |_, _| panic!());
+    assert!(!g.contains_node(6));
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn gen_gnp() {
+    let mut rng = rand::thread_rng();
+    let nodes = [1, 3, 5, 7, 9];
+    let g = UnGraphMap::<_, ()>::gen_gnp(&nodes, 1.0, &mut rng);
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g, g.complement(()).complement(()));
+    assert_eq!(g.edge_count(), 10);
+    assert_eq!(g.nodes().collect::<Vec<_>>(), nodes);
+
+    let g = DiGraphMap::<_, ()>::gen_gnp(&nodes, 1.0, &mut rng);
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.edge_count(), 20);
+    assert!(g.nodes().all(|n| !g.contains_edge(n, n)));
+
+    let g = DiGraphMap::<_, ()>::gen_gnp(&nodes, 0.0, &mut rng);
+    assert_eq!(g.node_count(), 5);
+    assert_eq!(g.edge_count(), 0);
+
+    let g = UnGraphMap::<_, u8>::gen_gnp(&nodes, 0.5, &mut rng);
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert!(g.all_edges().all(|(a, b, &w)| a != b && w == 0));
+}
+
+#[test]
+fn neighbors_where() {
+    let mut g = DiGraphMap::new();
+    g.add_edge("s", "a", 3);
+    g.add_edge("s", "b", 0);
+    g.add_edge("s", "c", -1);
+    g.add_edge("s", "s", 2);
+    g.add_edge("d", "s", 4);
+    let min = 0;
+    let mut positive = g.neighbors_where("s", |&w| w > min).collect::<Vec<_>>();
+    positive.sort();
+    assert_eq!(positive, vec!["a", "s"]);
+    assert_eq!(g.neighbors_where("x", |_| true).count(), 0);
+
+    let mut g = UnGraphMap::new();
+    g.add_edge(0, 1, 1);
+    g.add_edge(2, 0, 1);
+    g.add_edge(0, 3, 0);
+    let mut n = g.neighbors_where(0, |&w| w > 0).collect::<Vec<_>>();
+    n.sort();
+    assert_eq!(n, vec![1, 2]);
+}
+
+#[test]
+fn generic_edge_references() {
+    use petgraph::data::FromElements;
+    use petgraph::visit::{EdgeCount, EdgeRef, IntoEdgeReferences};
+
+    fn total_weight<G>(g: G) -> i32
+    where
+        G: IntoEdgeReferences<EdgeWeight = i32> + EdgeCount,
+    {
+        assert_eq!(g.edge_references().count(), g.edge_count());
+        g.edge_references().map(|e| *e.weight()).sum()
+    }
+
+    let g = UnGraphMap::<_, _>::from_edges(&[(0, 1, 2), (1, 2, 1), (2, 0, 4), (2, 3, 3)]);
+    assert_eq!(total_weight(&g), 10);
+    for e in g.edge_references() {
+        assert_eq!(e.id(), (e.source(), e.target()));
+        assert_eq!(g.edge_weight(e.source(), e.target()), Some(e.weight()));
+    }
+
+    let mst = UnGraphMap::<_, _>::from_elements(petgraph::algo::min_spanning_tree(&g));
+    assert_eq!(mst, g.minimum_spanning_tree());
+    assert_eq!(total_weight(&mst), 6);
+}
+
+#[test]
+fn into_graph_with_map() {
+    let mut gm = DiGraphMap::new();
+    gm.add_edge("shirt", "tie", 1);
+    gm.add_edge("tie", "jacket", 2);
+    gm.add_edge("trousers", "shoes", 3);
+    gm.add_edge("socks", "shoes", 4);
+    gm.add_edge("trousers", "belt", 5);
+    gm.add_edge("belt", "jacket", 6);
+    gm.add_node("watch");
+    let nodes = gm.nodes().collect::<Vec<_>>();
+    let (graph, indices) = gm.clone().into_graph_with_map::<u16>();
+    assert_eq!(graph.node_count(), 8);
+    assert_eq!(graph.edge_count(), 6);
+    assert_eq!(indices.keys().cloned().collect::<Vec<_>>(), nodes);
+    for (i, (&n, &ix)) in indices.iter().enumerate() {
+        assert_eq!(ix.index(), i);
+        assert_eq!(graph[ix], n);
+    }
+    assert_eq!(
+        graph[graph.find_edge(indices["belt"], indices["jacket"]).unwrap()],
+        6
+    );
+
+    let order = petgraph::algo::toposort(&graph, None)
+        .unwrap()
+        .into_iter()
+        .map(|ix| graph[ix])
+        .collect::<Vec<_>>();
+    let position = |n| order.iter().position(|&m| m == n).unwrap();
+    for (a, b, _) in gm.all_edges() {
+        assert!(position(a) < position(b));
+    }
+}
+
+#[test]
+fn two_coloring() {
+    let mut g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 0)]);
+    g.add_edge(10, 11, ());
+    g.add_node(20);
+    let colors = g.two_coloring().unwrap();
+    assert_eq!(colors.len(), g.node_count());
+    for (a, b, _) in g.all_edges() {
+        assert_ne!(colors[&a], colors[&b]);
+    }
+    assert!(!colors[&0]);
+    assert!(colors[&3]);
+    assert!(!colors[&10]);
+    assert!(!colors[&20]);
+
+    // odd cycle in one component
+    let mut odd = g.clone();
+    odd.add_edge(11, 12, ());
+    odd.add_edge(12, 10, ());
+    assert_eq!(odd.two_coloring(), None);
+
+    // self loop
+    g.add_edge(20, 20, ());
+    assert_eq!(g.two_coloring(), None);
+
+    assert_eq!(
+        UnGraphMap::<u8, ()>::new().two_coloring().map(|c| c.len()),
+        Some(0)
+    );
+}
+
+#[test]
+fn edge_weight_or_default_mut() {
+    let mut g = DiGraphMap::<_, u32>::new();
+    g.add_edge(0, 1, 5);
+    *g.edge_weight_or_default_mut(1, 2) += 1;
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.edge_weight(1, 2), Some(&1));
+    assert!(!g.contains_edge(2, 1));
+    *g.edge_weight_or_default_mut(0, 1) += 1;
+    assert_eq!(g.edge_weight(0, 1), Some(&6));
+    *g.edge_weight_or_default_mut(3, 3) += 2;
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.edge_weight(3, 3), Some(&2));
+    assert_eq!(g.node_count(), 4);
+    assert_eq!(g.edge_count(), 3);
+}
+
+#[test]
+fn node_bounds() {
+    let mut g = DiGraphMap::<_, ()>::from_nodes(vec![3, 7, 1, 9]);
+    assert_eq!(g.node_bounds(), Some((1, 9)));
+    g.remove_node(9);
+    g.add_edge(-2, 4, ());
+    assert_eq!(g.node_bounds(), Some((-2, 7)));
+    let g = DiGraphMap::<_, ()>::from_nodes(Some('x'));
+    assert_eq!(g.node_bounds(), Some(('x', 'x')));
+    assert_eq!(UnGraphMap::<u32, ()>::new().node_bounds(), None);
+}
+
+#[test]
+fn swap_node_keys() {
+    let mut g = DiGraphMap::new();
+    g.add_edge(0, 1, "0->1");
+    g.add_edge(1, 0, "1->0");
+    g.add_edge(0, 2, "0->2");
+    g.add_edge(3, 0, "3->0");
+    g.add_edge(0, 0, "0->0");
+    g.add_edge(1, 4, "1->4");
+    g.add_edge(2, 3, "2->3");
+    let nodes = g.nodes().collect::<Vec<_>>();
+    assert!(g.swap_node_keys(0, 1));
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.nodes().collect::<Vec<_>>(), nodes);
+    assert_eq!(g.edge_count(), 7);
+    assert_eq!(g.edge_weight(1, 0), Some(&"0->1"));
+    assert_eq!(g.edge_weight(0, 1), Some(&"1->0"));
+    assert_eq!(g.edge_weight(1, 2), Some(&"0->2"));
+    assert_eq!(g.edge_weight(3, 1), Some(&"3->0"));
+    assert_eq!(g.edge_weight(1, 1), Some(&"0->0"));
+    assert_eq!(g.edge_weight(0, 4), Some(&"1->4"));
+    assert_eq!(g.edge_weight(2, 3), Some(&"2->3"));
+    assert!(!g.contains_edge(0, 0));
+
+    let mut g = UnGraphMap::new();
+    g.add_edge(0, 1, 1);
+    g.add_edge(1, 2, 12);
+    g.add_edge(2, 3, 23);
+    assert!(g.swap_node_keys(3, 1));
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.edge_weight(0, 3), Some(&1));
+    assert_eq!(g.edge_weight(3, 2), Some(&12));
+    assert_eq!(g.edge_weight(2, 1), Some(&23));
+    assert_eq!(g.edge_count(), 3);
+
+    assert!(g.swap_node_keys(2, 2));
+    assert!(!g.swap_node_keys(2, 5));
+    assert!(!g.contains_node(5));
+}
+
+#[test]
+fn from_adjacency_lists() {
+    let lists = vec![
+        ("a", vec!["b", "c"]),
+        ("b", vec!["a", "d"]),
+        ("c", vec!["c"]),
+        ("e", vec![]),
+    ];
+    let edges = [("a", "b"), ("a", "c"), ("b", "a"), ("b", "d"), ("c", "c")];
+
+    let g = DiGraphMap::<_, ()>::from_adjacency_lists(lists.clone());
+    assert_eq!(g.check_invariants(), Ok(()));
+    let mut expected = DiGraphMap::from_edges(&edges);
+    expected.add_node("e");
+    assert_eq!(g, expected);
+    assert_eq!(g.edge_count(), 5);
+
+    let g = UnGraphMap::<_, ()>::from_adjacency_lists(lists);
+    assert_eq!(g.check_invariants(), Ok(()));
+    let mut expected = UnGraphMap::from_edges(&edges);
+    expected.add_node("e");
+    assert_eq!(g, expected);
+    assert_eq!(g.edge_count(), 4);
+}
+
+#[test]
+fn eccentricities() {
+    let g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4)]);
+    let ecc = g.eccentricities();
+    assert_eq!(
+        ecc.iter().map(|(&n, &e)| (n, e)).collect::<Vec<_>>(),
+        vec![(0, 4), (1, 3), (2, 2), (3, 3), (4, 4)]
+    );
+    assert_eq!(g.diameter(), Some(4));
+
+    // direction is honored
+    let g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4)]);
+    let ecc = g.eccentricities();
+    assert_eq!(
+        ecc.values().cloned().collect::<Vec<_>>(),
+        vec![4, 3, 2, 1, 0]
+    );
+    assert_eq!(g.diameter(), None);
+
+    let mut g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 1)]);
+    assert_eq!(g.diameter(), Some(1));
+    g.add_node(2);
+    assert_eq!(g.eccentricities()[&2], 0);
+    assert_eq!(g.diameter(), None);
+    assert_eq!(UnGraphMap::<_, ()>::from_nodes(Some(0)).diameter(), Some(0));
+    assert_eq!(UnGraphMap::<u8, ()>::new().diameter(), None);
+}
+
+#[test]
+fn try_add_edge() {
+    let mut g = UnGraphMap::new();
+    assert_eq!(g.try_add_edge(1, 2, 12), Ok(()));
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.try_add_edge(2, 1, 21), Err(21));
+    assert_eq!(g.edge_weight(1, 2), Some(&12));
+    assert_eq!(g.try_add_edge(3, 3, 33), Ok(()));
+    assert_eq!(g.try_add_edge(3, 3, 0), Err(0));
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.edge_count(), 2);
+    assert_eq!(g.neighbors(1).count(), 1);
+
+    let mut g = DiGraphMap::new();
+    assert_eq!(g.try_add_edge(1, 2, 12), Ok(()));
+    assert_eq!(g.try_add_edge(2, 1, 21), Ok(()));
+    assert_eq!(g.try_add_edge(1, 2, 0), Err(0));
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.edge_weight(1, 2), Some(&12));
+    assert_eq!(g.edge_count(), 2);
+}
+
+#[test]
+fn neighbors_undirected() {
+    let mut g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (2, 0), (1, 0)]);
+    let mut n = g.neighbors_undirected(0).collect::<Vec<_>>();
+    n.sort();
+    assert_eq!(n, vec![1, 2]);
+    g.add_edge(0, 0, ());
+    g.add_edge(0, 2, ());
+    let mut n = g.neighbors_undirected(0).collect::<Vec<_>>();
+    n.sort();
+    assert_eq!(n, vec![0, 1, 2]);
+    assert_eq!(g.neighbors_undirected(2).collect::<Vec<_>>(), vec![0]);
+    assert_eq!(g.neighbors_undirected(3).count(), 0);
+}
+
+#[test]
+fn from_edges_dedup() {
+    let edges = vec![
+        (1, 2, 5),
+        (2, 1, 3),
+        (2, 3, 1),
+        (1, 2, 2),
+        (3, 3, 4),
+        (3, 3, 1),
+    ];
+    let g = UnGraphMap::from_edges_dedup(edges.clone(), |w, other| *w = cmp::max(*w, other));
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.edge_count(), 3);
+    assert_eq!(g[(1, 2)], 5);
+    assert_eq!(g[(3, 2)], 1);
+    assert_eq!(g[(3, 3)], 4);
+
+    let g = DiGraphMap::from_edges_dedup(edges, |w, other| *w += other);
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.edge_count(), 4);
+    assert_eq!(g[(1, 2)], 7);
+    assert_eq!(g[(2, 1)], 3);
+    assert_eq!(g[(3, 3)], 5);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn par_all_pairs_dijkstra() {
+    let mut g = DiGraphMap::new();
+    for &(a, b, w) in &[
+        (0, 1, 7),
+        (0, 2, 9),
+        (0, 5, 14),
+        (1, 2, 10),
+        (1, 3, 15),
+        (2, 3, 11),
+    ] {
+        g.add_edge(a, b, w);
+    }
+    for &(a, b, w) in &[(2, 5, 2), (3, 4, 6), (4, 5, 9), (5, 4, 1), (4, 0, 3)] {
+        g.add_edge(a, b, w);
+    }
+    g.add_node(6);
+    let costs = g.par_all_pairs_dijkstra(|_, _, &w| w);
+    assert_eq!(
+        costs.keys().cloned().collect::<Vec<_>>(),
+        g.nodes().collect::<Vec<_>>()
+    );
+    for n in g.nodes() {
+        assert_eq!(costs[&n], g.dijkstra(n, |_, _, &w| w));
+    }
+    assert_eq!(costs[&6].len(), 1);
+    assert_eq!(costs[&1][&0], 16);
+}
+
+#[test]
+fn remove_stable() {
+    let mut g = DiGraphMap::<_, ()>::from_nodes(0..5);
+    assert!(g.remove_node_stable(2));
+    assert!(!g.remove_node_stable(2));
+    assert_eq!(g.nodes().collect::<Vec<_>>(), vec![0, 1, 3, 4]);
+
+    let edges = [
+        (0, 1),
+        (1, 2),
+        (2, 0),
+        (0, 2),
+        (2, 2),
+        (3, 0),
+        (0, 4),
+        (2, 4),
+        (4, 0),
+    ];
+    let mut g = DiGraphMap::<_, ()>::from_edges(&edges);
+    assert!(g.remove_node_stable(2));
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.nodes().collect::<Vec<_>>(), vec![0, 1, 3, 4]);
+    let all_edges =
+        |g: &DiGraphMap<_, _>| g.all_edges().map(|(a, b, _)| (a, b)).collect::<Vec<_>>();
+    assert_eq!(all_edges(&g), vec![(0, 1), (3, 0), (0, 4), (4, 0)]);
+    assert_eq!(
+        g.neighbors_directed(0, Outgoing).collect::<Vec<_>>(),
+        vec![1, 4]
+    );
+    assert_eq!(
+        g.neighbors_directed(0, Incoming).collect::<Vec<_>>(),
+        vec![3, 4]
+    );
+
+    assert_eq!(g.remove_edge_stable(0, 1), Some(()));
+    assert_eq!(g.remove_edge_stable(0, 1), None);
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(all_edges(&g), vec![(3, 0), (0, 4), (4, 0)]);
+    assert_eq!(
+        g.neighbors_directed(0, Incoming).collect::<Vec<_>>(),
+        vec![3, 4]
+    );
+
+    let mut g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (1, 1), (2, 3), (3, 1), (0, 3)]);
+    assert_eq!(g.remove_edge_stable(2, 1), Some(()));
+    assert_eq!(g.remove_edge_stable(1, 1), Some(()));
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.neighbors(1).collect::<Vec<_>>(), vec![0, 3]);
+    assert!(g.remove_node_stable(1));
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.nodes().collect::<Vec<_>>(), vec![0, 2, 3]);
+    assert_eq!(
+        g.all_edges().map(|(a, b, _)| (a, b)).collect::<Vec<_>>(),
+        vec![(2, 3), (0, 3)]
+    );
+}
+
+#[test]
+fn articulation_points_bridges() {
+    let mut g = UnGraphMap::<_, ()>::from_edges(&[
+        (0, 1),
+        (1, 2),
+        (2, 0),
+        (2, 3),
+        (3, 4),
+        (4, 5),
+        (5, 3),
+        (4, 4),
+    ]);
+    assert_eq!(g.articulation_points(), vec![2, 3]);
+    assert_eq!(g.bridges(), vec![(2, 3)]);
+
+    // a second component, with a pendant path
+    g.add_edge(10, 11, ());
+    g.add_edge(12, 11, ());
+    g.add_edge(13, 13, ());
+    assert_eq!(g.articulation_points(), vec![2, 3, 11]);
+    let mut bridges = g.bridges();
+    bridges.sort();
+    assert_eq!(bridges, vec![(2, 3), (10, 11), (11, 12)]);
+
+    // the root of the search is a cut vertex
+    let g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (0, 2)]);
+    assert_eq!(g.articulation_points(), vec![0]);
+    assert_eq!(g.bridges().len(), 2);
+    let g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    assert!(g.articulation_points().is_empty());
+    assert!(g.bridges().is_empty());
+}
+
+#[test]
+fn edge_weight_stats() {
+    let mut g = DiGraphMap::new();
+    g.add_edge("a", "b", 1.);
+    g.add_edge("b", "c", 5.);
+    g.add_edge("c", "a", 3.);
+    assert_eq!(g.total_edge_weight(), 9.);
+    assert_eq!(g.min_edge(), Some(("a", "b", &1.)));
+    assert_eq!(g.max_edge(), Some(("b", "c", &5.)));
+
+    g.add_edge("c", "d", 5.);
+    g.add_edge("d", "d", f64::NAN);
+    assert_eq!(g.max_edge(), Some(("b", "c", &5.)));
+    assert_eq!(g.min_edge(), Some(("a", "b", &1.)));
+
+    // an incomparable first weight is skipped too
+    let g = UnGraphMap::<_, _>::from_edges(&[(0, 0, f64::NAN), (0, 1, 2.), (1, 2, 1.)]);
+    assert_eq!(g.max_edge(), Some((0, 1, &2.)));
+    assert_eq!(g.min_edge(), Some((1, 2, &1.)));
+
+    let g = UnGraphMap::<u8, u32>::new();
+    assert_eq!(g.total_edge_weight(), 0);
+    assert_eq!(g.min_edge(), None);
+    assert_eq!(g.max_edge(), None);
+}
+
+#[test]
+fn drain_nodes() {
+    let mut g = DiGraphMap::<_, ()>::from_edges(&[
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (3, 3),
+        (4, 2),
+        (2, 4),
+        (0, 4),
+        (5, 0),
+    ]);
+    g.add_node(7);
+    assert_eq!(g.drain_nodes(|n| n % 2 == 1), vec![1, 3, 5, 7]);
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.nodes().collect::<Vec<_>>(), vec![0, 2, 4]);
+    assert_eq!(
+        g.all_edges().map(|(a, b, _)| (a, b)).collect::<Vec<_>>(),
+        vec![(4, 2), (2, 4), (0, 4)]
+    );
+    assert_eq!(
+        g.neighbors_directed(4, Incoming).collect::<Vec<_>>(),
+        vec![2, 0]
+    );
+    assert!(g.drain_nodes(|_| false).is_empty());
+    assert_eq!(g.drain_nodes(|_| true), vec![0, 2, 4]);
+    assert_eq!(g.node_count(), 0);
+    assert_eq!(g.edge_count(), 0);
+}
+
+#[test]
+fn is_subgraph_of() {
+    let mut g = UnGraphMap::new();
+    g.add_edge(0, 1, 'a');
+    g.add_edge(1, 2, 'b');
+    g.add_edge(2, 2, 'c');
+    g.add_node(3);
+    assert!(g.is_subgraph_of(&g));
+
+    // insertion order doesn't matter
+    let mut h = UnGraphMap::new();
+    h.add_node(3);
+    h.add_edge(2, 2, 'c');
+    h.add_edge(2, 1, 'b');
+    assert!(h.is_subgraph_of(&g));
+    assert!(!g.is_subgraph_of(&h));
+
+    h.add_edge(0, 3, 'd');
+    assert!(!h.is_subgraph_of(&g));
+    h.remove_edge(0, 3);
+    h.remove_node(0);
+    *h.edge_weight_mut(1, 2).unwrap() = 'x';
+    assert!(!h.is_subgraph_of(&g));
+
+    let g = DiGraphMap::<_, ()>::from_edges(&[(0, 1)]);
+    let h = DiGraphMap::<_, ()>::from_edges(&[(1, 0)]);
+    assert!(!h.is_subgraph_of(&g));
+    assert!(DiGraphMap::new().is_subgraph_of(&g));
+}
+
+#[test]
+fn redirect_edges() {
+    let mut g = DiGraphMap::new();
+    g.add_edge(0, 1, "0->1");
+    g.add_edge(2, 1, "2->1");
+    g.add_edge(2, 3, "2->3");
+    g.add_edge(1, 3, "1->3");
+    g.add_edge(3, 0, "3->0");
+    // collapse 2 into 1
+    g.redirect_edges(|n| if n == 2 { 1 } else { n });
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.node_count(), 4);
+    assert_eq!(g.edge_count(), 4);
+    assert_eq!(g.degree(2), 0);
+    assert_eq!(g[(0, 1)], "0->1");
+    assert_eq!(g[(1, 1)], "2->1");
+    assert_eq!(g[(1, 3)], "1->3");
+    assert_eq!(g[(3, 0)], "3->0");
+
+    // relabel into a new node
+    g.redirect_edges(|n| if n == 0 { 5 } else { n });
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.node_count(), 5);
+    assert_eq!(
+        g.neighbors_directed(5, Incoming).collect::<Vec<_>>(),
+        vec![3]
+    );
+
+    let mut g = UnGraphMap::<_, _>::from_edges(&[(0, 1, 1), (1, 2, 2)]);
+    g.redirect_edges(|n| 10 - n);
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(
+        g.all_edges()
+            .map(|(a, b, &w)| (a, b, w))
+            .collect::<Vec<_>>(),
+        vec![(9, 10, 1), (8, 9, 2)]
+    );
+}
+
+#[test]
+fn edge_difference() {
+    let mut g = UnGraphMap::new();
+    g.add_edge("a", "b", 1);
+    g.add_edge("b", "c", 2);
+    g.add_edge("c", "c", 3);
+    let mut h = g.clone();
+    h.remove_edge("c", "b");
+    *h.edge_weight_mut("a", "b").unwrap() = 10;
+    h.add_node("d");
+    assert_eq!(
+        g.edge_difference(&h).collect::<Vec<_>>(),
+        vec![("b", "c", &2)]
+    );
+    assert_eq!(h.edge_difference(&g).count(), 0);
+    assert_eq!(g.edge_difference(&g).count(), 0);
+
+    h.add_edge("d", "a", 4);
+    assert_eq!(
+        g.symmetric_edge_difference(&h).collect::<Vec<_>>(),
+        vec![("b", "c", &2), ("a", "d", &4)]
+    );
+}
+
+#[test]
+fn add_or_update_edge() {
+    let mut g = DiGraphMap::new();
+    *g.add_or_update_edge("a", "b", 1) *= 5;
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.edge_weight("a", "b"), Some(&5));
+    *g.add_or_update_edge("a", "b", 2) += 1;
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.edge_weight("a", "b"), Some(&3));
+    assert_eq!(g.edge_count(), 1);
+    assert_eq!(g.neighbors("a").count(), 1);
+    *g.add_or_update_edge("b", "b", 0) -= 1;
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.edge_weight("b", "b"), Some(&-1));
+    assert!(!g.contains_edge("b", "a"));
+}
+
+#[test]
+fn self_loops() {
+    let mut g = DiGraphMap::new();
+    g.add_edge(0, 1, 1);
+    g.add_edge(1, 1, 11);
+    g.add_edge(1, 0, 10);
+    g.add_edge(3, 3, 33);
+    g.add_edge(2, 3, 23);
+    g.add_edge(0, 0, 0);
+    assert_eq!(
+        g.self_loops().collect::<Vec<_>>(),
+        vec![(0, &0), (1, &11), (3, &33)]
+    );
+    assert_eq!(g.remove_self_loops(), 3);
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.self_loops().count(), 0);
+    assert_eq!(g.remove_self_loops(), 0);
+    let mut edges = g.all_edges().map(|(a, b, _)| (a, b)).collect::<Vec<_>>();
+    edges.sort();
+    assert_eq!(edges, vec![(0, 1), (1, 0), (2, 3)]);
+    assert_eq!(g.node_count(), 4);
+
+    let mut g = UnGraphMap::<_, ()>::from_edges(&[(0, 0), (0, 1), (1, 1)]);
+    assert_eq!(g.remove_self_loops(), 2);
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.neighbors(0).collect::<Vec<_>>(), vec![1]);
+}
+
+#[test]
+fn is_isomorphic_to() {
+    let triangle = UnGraphMap::<_, ()>::from_edges(&[('a', 'b'), ('b', 'c'), ('c', 'a')]);
+    let relabeled = UnGraphMap::<_, ()>::from_edges(&[('z', 'x'), ('y', 'z'), ('x', 'y')]);
+    let path = UnGraphMap::<_, ()>::from_edges(&[('a', 'b'), ('b', 'c')]);
+    assert!(triangle.is_isomorphic_to(&relabeled));
+    assert!(!triangle.is_isomorphic_to(&path));
+    assert!(!path.is_isomorphic_to(&triangle));
+
+    // same degree sequence, not isomorphic: a hexagon and two triangles
+    let hexagon =
+        UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 0)]);
+    let triangles =
+        UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)]);
+    assert_eq!(hexagon.degree_sequence(), triangles.degree_sequence());
+    assert!(!hexagon.is_isomorphic_to(&triangles));
+
+    // direction is honored
+    let cycle = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    let mut reversed = cycle.clone();
+    reversed.reverse();
+    let transitive = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (0, 2)]);
+    assert!(cycle.is_isomorphic_to(&reversed));
+    assert!(!cycle.is_isomorphic_to(&transitive));
+
+    let mut looped = cycle.clone();
+    looped.add_edge(1, 1, ());
+    let mut other = reversed;
+    other.add_edge(2, 2, ());
+    assert!(looped.is_isomorphic_to(&other));
+}
+
+#[test]
+fn graph_view() {
+    use petgraph::visit::{IntoEdgeReferences, IntoNeighbors, NodeCount};
+
+    let gm = DiGraphMap::<_, Vec<u8>>::from_edges(&[
+        (1, 2, vec![1]),
+        (2, 3, vec![2]),
+        (3, 1, vec![3]),
+        (1, 4, vec![4]),
+        (4, 4, vec![5]),
+    ]);
+    let view = gm.graph_view::<u32>();
+    assert_eq!(view.node_count(), gm.node_count());
+    for n in gm.nodes() {
+        let ix = view.node_index(n).unwrap();
+        assert_eq!(view.node(ix), Some(n));
+        let via_view: Vec<_> = view.neighbors(ix).map(|i| view.node(i).unwrap()).collect();
+        let direct: Vec<_> = gm.neighbors(n).collect();
+        assert_eq!(via_view, direct);
+        for (a, b, w) in view.edges(ix) {
+            let (a, b) = (view.node(a).unwrap(), view.node(b).unwrap());
+            assert!(std::ptr::eq(w, gm.edge_weight(a, b).unwrap()));
+        }
+    }
+    assert_eq!(view.node_index(5), None);
+    assert_eq!(view.edge_references().count(), gm.edge_count());
+
+    let reachable: HashSet<_> = Dfs::new(view, view.node_index(2).unwrap())
+        .iter(view)
+        .map(|ix| view.node(ix).unwrap())
+        .collect();
+    assert_eq!(reachable, gm.nodes().collect());
+}
+
+#[test]
+fn visit_mut_ref() {
+    use petgraph::visit::{IntoNodeIdentifiers, NodeCount};
+
+    fn count_nodes<G: NodeCount>(g: G) -> usize {
+        g.node_count()
+    }
+    fn collect_nodes<G, N>(g: &mut G) -> Vec<N>
+    where
+        for<'a> &'a G: IntoNodeIdentifiers<NodeId = N>,
+    {
+        let g = &*g;
+        g.node_identifiers().collect()
+    }
+
+    let mut gm = DiGraphMap::<_, ()>::from_edges(&[(1, 2), (2, 3)]);
+    gm.add_node(4);
+    assert_eq!(count_nodes(&mut gm), 4);
+    assert_eq!(collect_nodes(&mut gm), vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn clone_from() {
+    let source = DiGraphMap::<_, f32>::from_edges(&[(1, 2, 1.), (2, 3, 2.), (3, 3, 3.)]);
+
+    let mut g = DiGraphMap::with_capacity(64, 64);
+    g.clone_from(&source);
+    assert_eq!(g, source);
+    assert_eq!(g.nodes().collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert!(g.capacity().0 >= 64);
+    assert!(g.capacity().1 >= 64);
+    assert_eq!(g.check_invariants(), Ok(()));
+
+    let mut g = DiGraphMap::from_edges(&[(5, 4, 9.), (4, 1, 8.), (1, 2, 7.)]);
+    for i in 0..3 {
+        let mut source = source.clone();
+        source.add_edge(10, i, 0.);
+        g.clone_from(&source);
+        assert_eq!(g, source);
+        assert!(!g.contains_node(5));
+        assert!(!g.contains_edge(4, 1));
+        assert_eq!(g.edge_weight(1, 2), Some(&1.));
+        assert_eq!(g.edge_count(), 4);
+        assert_eq!(g.check_invariants(), Ok(()));
+    }
+}
+
+#[test]
+fn pagerank() {
+    fn assert_close(rank: f64, expected: f64) {
+        assert!((rank - expected).abs() < 1e-6, "{} != {}", rank, expected);
+    }
+
+    let g = DiGraphMap::from_edges(&[
+        ('a', 'b', 1.),
+        ('a', 'c', 1.),
+        ('b', 'c', 1.),
+        ('c', 'a', 1.),
+        ('d', 'c', 1.),
+    ]);
+    let rank = g.pagerank(0.85, 100);
+    assert_close(rank[&'a'], 0.372527);
+    assert_close(rank[&'b'], 0.195824);
+    assert_close(rank[&'c'], 0.394149);
+    assert_close(rank[&'d'], 0.0375);
+    assert_close(rank.values().sum(), 1.);
+
+    // weighted edges, and 3 is a dangling node
+    let g = DiGraphMap::from_edges(&[(1, 2, 1.), (1, 3, 3.), (2, 3, 1.)]);
+    let rank = g.pagerank(0.85, 100);
+    assert_close(rank[&1], 0.204892);
+    assert_close(rank[&2], 0.248431);
+    assert_close(rank[&3], 0.546677);
+    assert_close(rank.values().sum(), 1.);
+
+    assert!(DiGraphMap::<u8, f64>::new().pagerank(0.85, 10).is_empty());
+}
+
+#[test]
+fn k_hop_neighbors() {
+    let path = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4)]);
+    let hops = path.k_hop_neighbors(0, 2);
+    assert_eq!(
+        hops.into_iter().collect::<Vec<_>>(),
+        vec![(0, 0), (1, 1), (2, 2)]
+    );
+    assert_eq!(path.k_hop_neighbors(2, 0).len(), 1);
+    assert_eq!(path.k_hop_neighbors(2, 10).len(), 5);
+    assert!(path.k_hop_neighbors(7, 2).is_empty());
+
+    let g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (3, 2), (2, 0)]);
+    let hops = g.k_hop_neighbors(2, 5);
+    assert_eq!(hops.len(), 3);
+    assert_eq!(hops[&1], 2);
+    assert!(!hops.contains_key(&3));
+}
+
+#[test]
+fn into_undirected_directed() {
+    let g = DiGraphMap::from_edges(&[(1, 2, 5), (2, 1, 6)]);
+    let ug = g.into_undirected();
+    assert_eq!(ug.node_count(), 2);
+    assert_eq!(ug.edge_count(), 1);
+    assert_eq!(ug[(2, 1)], 6);
+    assert_eq!(ug.neighbors(1).collect::<Vec<_>>(), vec![2]);
+    assert_eq!(ug.check_invariants(), Ok(()));
+
+    let dg = ug.into_directed();
+    assert_eq!(dg.edge_count(), 2);
+    assert_eq!(dg[(1, 2)], 6);
+    assert_eq!(dg[(2, 1)], 6);
+    assert_eq!(
+        dg.neighbors_directed(1, Incoming).collect::<Vec<_>>(),
+        vec![2]
+    );
+    assert_eq!(dg.check_invariants(), Ok(()));
+
+    let mut g = DiGraphMap::from_edges(&[(1, 2, 5), (2, 1, 6), (3, 3, 1), (3, 1, 2)]);
+    g.add_node(4);
+    let ug = g.into_undirected_with(|w, new| *w = cmp::min(*w, new));
+    assert_eq!(ug.nodes().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    assert_eq!(ug.edge_count(), 3);
+    assert_eq!(ug[(1, 2)], 5);
+    assert_eq!(ug[(3, 3)], 1);
+    assert_eq!(ug.check_invariants(), Ok(()));
+    let dg = ug.into_directed();
+    assert_eq!(dg.edge_count(), 5);
+    assert_eq!(dg.check_invariants(), Ok(()));
+}
+
+#[test]
+fn edges_mut() {
+    let mut g = DiGraphMap::<_, i32>::from_edges(&[(1, 2, 1), (2, 3, 2), (3, 2, 3), (2, 2, 4)]);
+    for (a, _, weight) in g.edges_mut(2) {
+        assert_eq!(a, 2);
+        *weight *= 10;
+    }
+    let mut edges = g
+        .all_edges()
+        .map(|(a, b, &w)| (a, b, w))
+        .collect::<Vec<_>>();
+    edges.sort();
+    assert_eq!(edges, vec![(1, 2, 1), (2, 2, 40), (2, 3, 20), (3, 2, 3)]);
+    assert_eq!(g.edges_mut(7).count(), 0);
+
+    let mut g = UnGraphMap::<_, i32>::from_edges(&[(1, 2, 1), (3, 2, 2), (2, 2, 3), (1, 3, 4)]);
+    let mut targets = g
+        .edges_mut(2)
+        .map(|(a, b, weight)| {
+            assert_eq!(a, 2);
+            *weight += 100;
+            b
+        })
+        .collect::<Vec<_>>();
+    targets.sort();
+    assert_eq!(targets, vec![1, 2, 3]);
+    assert_eq!(g[(2, 1)], 101);
+    assert_eq!(g[(2, 3)], 102);
+    assert_eq!(g[(2, 2)], 103);
+    assert_eq!(g[(1, 3)], 4);
+}
+
+#[test]
+fn into_nodes_into_edges() {
+    let mut g = DiGraphMap::new();
+    g.add_edge("b", "a", String::from("ba"));
+    g.add_edge("a", "c", String::from("ac"));
+    g.add_edge("c", "c", String::from("cc"));
+    g.add_node("d");
+
+    let nodes = g.clone().into_nodes();
+    assert_eq!(nodes.len(), 4);
+    assert_eq!(nodes.rev().collect::<Vec<_>>(), vec!["d", "c", "a", "b"]);
+
+    let expected = g
+        .all_edges()
+        .map(|(a, b, w)| (a, b, w.clone()))
+        .collect::<Vec<_>>();
+    let edges = g.into_edges();
+    assert_eq!(edges.len(), 3);
+    assert_eq!(edges.collect::<Vec<_>>(), expected);
+
+    let g = UnGraphMap::<_, ()>::from_edges(&[(3, 1)]);
+    assert_eq!(g.into_edges().next(), Some((1, 3, ())));
+}
+
+#[test]
+fn get_node() {
+    let names = [String::from("x"), String::from("y"), String::from("z")];
+    let mut g = UnGraphMap::new();
+    g.add_edge(names[0].as_str(), names[1].as_str(), 1);
+    g.add_edge(names[1].as_str(), names[2].as_str(), 2);
+
+    let query = String::from("y");
+    let y = g.get_node(query.as_str()).unwrap();
+    drop(query);
+    assert_eq!(g.neighbors(y).count(), 2);
+    assert_eq!(g.edge_weight("z", y), Some(&2));
+    assert_eq!(g.get_node("w"), None);
+
+    let g = DiGraphMap::<_, ()>::from_edges(&[(1u32, 2)]);
+    assert_eq!(g.get_node(&2), Some(2));
+}
+
+#[test]
+fn set_operations() {
+    let g = DiGraphMap::<_, i32>::from_edges(&[(0, 1, 1), (1, 2, 2), (2, 2, 3), (2, 0, 4)]);
+    let mut h = DiGraphMap::<_, i32>::from_edges(&[(1, 2, 10), (0, 2, 20), (2, 2, 30)]);
+    h.add_node(5);
+
+    let u = g.union(&h, |w, new| *w += new);
+    assert_eq!(u.node_count(), 4);
+    assert_eq!(u.edge_count(), 5);
+    assert_eq!(u[(1, 2)], 12);
+    assert_eq!(u[(2, 2)], 33);
+    assert_eq!(u[(0, 2)], 20);
+    assert_eq!(u[(2, 0)], 4);
+    assert_eq!(u.check_invariants(), Ok(()));
+
+    let i = g.intersection(&h, |a, b| a * b);
+    assert_eq!(i.nodes().collect::<Vec<_>>(), vec![0, 1, 2]);
+    let mut edges = i
+        .all_edges()
+        .map(|(a, b, &w)| (a, b, w))
+        .collect::<Vec<_>>();
+    edges.sort();
+    assert_eq!(edges, vec![(1, 2, 20), (2, 2, 90)]);
+    assert_eq!(i.check_invariants(), Ok(()));
+
+    let d = g.difference(&h);
+    assert_eq!(d.node_count(), 3);
+    let mut edges = d
+        .all_edges()
+        .map(|(a, b, &w)| (a, b, w))
+        .collect::<Vec<_>>();
+    edges.sort();
+    assert_eq!(edges, vec![(0, 1, 1), (2, 0, 4)]);
+    assert_eq!(d.check_invariants(), Ok(()));
+    assert!(d.is_subgraph_of(&g));
+    assert_eq!(d.union(&i, |_, _| unreachable!()).edge_count(), 4);
+}
+
+#[test]
+fn complement() {
+    let path = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+    let c = path.complement(());
+    assert_eq!(c.node_count(), 4);
+    let mut edges = c.all_edges().map(|(a, b, _)| (a, b)).collect::<Vec<_>>();
+    edges.sort();
+    assert_eq!(edges, vec![(0, 2), (0, 3), (1, 3)]);
+    assert_eq!(c.complement(()), path);
+
+    let mut path = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+    path.add_edge(3, 3, ());
+    let c = path.complement(());
+    assert_eq!(c.edge_count(), 4 * 3 - 3);
+    for a in path.nodes() {
+        assert!(!c.contains_edge(a, a));
+        for b in path.nodes() {
+            if a != b {
+                assert!(path.contains_edge(a, b) != c.contains_edge(a, b));
+            }
+        }
+    }
+}
+
+#[test]
+fn graphmap_hash() {
+    use std::collections::HashMap;
+
+    let a = UnGraphMap::<_, i32>::from_edges(&[(1, 2, 12), (2, 3, 23), (3, 3, 33)]);
+    let mut b = UnGraphMap::new();
+    b.add_edge(3, 3, 33);
+    b.add_edge(3, 2, 23);
+    b.add_edge(2, 1, 12);
+    let mut c = a.clone();
+    c[(1, 2)] = 21;
+
+    let mut cache = HashMap::new();
+    cache.insert(a, "a");
+    assert_eq!(cache.insert(b, "b"), Some("a"));
+    assert_eq!(cache.insert(c, "c"), None);
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+fn retain_nodes() {
+    let mut edges = Vec::new();
+    for i in 0..10 {
+        edges.push((i, (i + 1) % 10));
+        edges.push(((i + 3) % 10, i));
+    }
+    edges.push((4, 4));
+    edges.push((5, 5));
+
+    let mut g = DiGraphMap::<_, ()>::from_edges(&edges);
+    g.retain_nodes(|_, n| n % 2 == 0);
+    assert_eq!(g.node_count(), 5);
+    assert!(g.nodes().all(|n| n % 2 == 0));
+    assert_eq!(g.edge_count(), 1);
+    assert!(g.contains_edge(4, 4));
+    assert_eq!(g.check_invariants(), Ok(()));
+
+    let mut g = UnGraphMap::<_, ()>::from_edges(&edges);
+    g.retain_nodes(|_, n| n % 2 == 0);
+    assert_eq!(g.node_count(), 5);
+    assert_eq!(g.check_invariants(), Ok(()));
+    assert_eq!(g.edge_count(), 1);
+
+    // the closure sees the graph in its current state
+    let mut g = UnGraphMap::<_, ()>::from_edges(&edges);
+    g.retain_nodes(|g, n| g.neighbors(n).count() > 2);
+    assert_eq!(g.check_invariants(), Ok(()));
+}
+
+#[test]
+fn retain_edges() {
+    let mut g = DiGraphMap::new();
+    for i in 0..10 {
+        g.add_edge(i, (i + 1) % 10, i);
+        g.add_edge((i + 1) % 10, i, i);
+        g.add_edge(i, i, i);
+    }
+    g.retain_edges(|g, a, b| a < b && g[(a, b)] % 2 == 0);
+    assert_eq!(g.node_count(), 10);
+    assert_eq!(g.edge_count(), 5);
+    assert!(g.all_edges().all(|(a, b, &w)| a < b && w % 2 == 0));
+    assert_eq!(g.check_invariants(), Ok(()));
+
+    let mut g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 2), (2, 0)]);
+    g.retain_edges(|_, a, b| a != b);
+    assert_eq!(g.edge_count(), 3);
+    assert_eq!(g.check_invariants(), Ok(()));
+}
+
+#[test]
+fn retain_edges_mut() {
+    let mut g = DiGraphMap::new();
+    for i in 0..10 {
+        g.add_edge(i, (i + 1) % 10, i);
+        g.add_edge((i + 1) % 10, i, i);
+        g.add_edge(i, i, i);
+    }
+    g.retain_edges_mut(|a, b, w| {
+        *w *= 10;
+        a < b && *w % 20 == 0
+    });
+    assert_eq!(g.node_count(), 10);
+    assert_eq!(g.edge_count(), 5);
+    assert!(g.all_edges().all(|(a, b, &w)| a < b && w == 10 * a));
+    assert_eq!(g.check_invariants(), Ok(()));
+
+    let mut g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 2), (2, 0)]);
+    g.retain_edges_mut(|a, b, _| a != b);
+    assert_eq!(g.edge_count(), 3);
+    assert_eq!(g.neighbors(2).count(), 2);
+    assert_eq!(g.check_invariants(), Ok(()));
+    g.retain_edges_mut(|_, _, _| true);
+    assert_eq!(g.edge_count(), 3);
+}
+
+#[test]
+fn custom_hasher() {
+    use petgraph::graphmap::MultiGraphMap;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::BuildHasherDefault;
+
+    type FixedState = BuildHasherDefault<DefaultHasher>;
+
+    let mut g =
+        GraphMap::<_, _, Directed, _>::with_capacity_and_hasher(4, 4, FixedState::default());
+    g.add_edge("a", "b", 1);
+    g.add_edge("b", "c", 2);
+    g.add_edge("a", "c", 5);
+    assert!(g.contains_edge("a", "b"));
+    assert!(!g.contains_edge("b", "a"));
+    assert_eq!(g.edge_weight("b", "c"), Some(&2));
+    assert_eq!(g.edges("a").count(), 2);
+    let scores = dijkstra(&g, "a", None, |e| *e.weight());
+    assert_eq!(scores["c"], 3);
+
+    // the same hasher gives the same iteration order
+    let h: GraphMap<_, _, Directed, FixedState> =
+        GraphMap::from_edges(&[("a", "b", 1), ("b", "c", 2), ("a", "c", 5)]);
+    assert!(g.nodes().eq(h.nodes()));
+    assert!(g.all_edges().eq(h.all_edges()));
+
+    let mut m =
+        MultiGraphMap::<_, _, Directed, _>::with_capacity_and_hasher(4, 4, FixedState::default());
+    m.add_edge("a", "b", 1);
+    m.add_edge("a", "b", 2);
+    m.add_edge("b", "c", 3);
+    assert_eq!(m.edge_multiplicity("a", "b"), 2);
+    assert_eq!(m.edges("a").count(), 2);
+    let n: MultiGraphMap<_, _, Directed, FixedState> =
+        MultiGraphMap::from_edges(&[("a", "b", 1), ("a", "b", 2), ("b", "c", 3)]);
+    assert!(m.nodes().eq(n.nodes()));
+    assert!(m.all_edges().eq(n.all_edges()));
+}
+
+#[test]
+fn generic_algorithms_directed() {
+    use petgraph::algo::bellman_ford;
+    use petgraph::visit::{EdgeRef, IntoEdges, IntoNeighborsDirected, NodeIndexable};
+
+    let mut g = DiGraphMap::new();
+    g.add_edge("a", "b", 4u32);
+    g.add_edge("a", "c", 1);
+    g.add_edge("c", "b", 2);
+    g.add_edge("b", "d", 1);
+    g.add_edge("d", "a", 10);
+    g.add_node("e");
+
+    let scores = dijkstra(&g, "a", None, |e| *e.weight());
+    let mut scores: Vec<_> = scores.into_iter().collect();
+    scores.sort();
+    assert_eq!(scores, vec![("a", 0), ("b", 3), ("c", 1), ("d", 4)]);
+
+    // edge references are keyed by the directed edge
+    for edge in IntoEdges::edges(&g, "a") {
+        assert_eq!(edge.source(), "a");
+        assert_eq!(edge.id(), (edge.source(), edge.target()));
+        assert_eq!(g.edge_weight("a", edge.target()), Some(edge.weight()));
+    }
+    let mut incoming: Vec<_> =
+        IntoNeighborsDirected::neighbors_directed(&g, "b", Incoming).collect();
+    incoming.sort();
+    assert_eq!(incoming, vec!["a", "c"]);
+
+    let fg = DiGraphMap::<_, f64>::from_edges(&[(0, 1, 4.), (0, 2, 1.), (2, 1, -2.), (1, 3, 1.)]);
+    let (distances, predecessors) = bellman_ford(&fg, 0).unwrap();
+    assert_eq!(distances[fg.to_index(3)], 0.);
+    assert_eq!(predecessors[fg.to_index(1)], Some(2));
+}
+
+#[test]
+fn neighbors_exact_size() {
+    let g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (0, 2), (3, 0), (0, 0)]);
+    let neighbors = g.neighbors(0);
+    assert_eq!(neighbors.len(), 4);
+    let forward: Vec<_> = g.neighbors(0).collect();
+    let mut backward: Vec<_> = g.neighbors(0).rev().collect();
+    backward.reverse();
+    assert_eq!(forward, backward);
+    let mut neighbors = g.neighbors(0);
+    neighbors.next();
+    neighbors.next_back();
+    assert_eq!(neighbors.len(), 2);
+    assert_eq!(g.neighbors(5).len(), 0);
+
+    let g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (2, 0), (3, 0)]);
+    assert_eq!(g.neighbors(0).size_hint(), (0, Some(3)));
+    assert_eq!(g.neighbors(0).count(), 1);
+}
+
+#[test]
+fn all_edges_mut_doubles_weights() {
+    let mut g = UnGraphMap::<_, f64>::from_edges(&[(0, 1, 1.), (2, 1, 0.5), (2, 2, 3.)]);
+    for (_, _, w) in g.all_edges_mut() {
+        *w *= 2.0;
+    }
+    assert_eq!(g.edge_weight(1, 0), Some(&2.));
+    assert_eq!(g.edge_weight(1, 2), Some(&1.));
+    assert_eq!(g.edge_weight(2, 2), Some(&6.));
+    assert_eq!(g.all_edges_mut().count(), 3);
+    assert_eq!(g.all_edges_mut().rev().count(), 3);
+}
+
+#[test]
+fn map_filter_map() {
+    let g = DiGraphMap::<_, f64>::from_edges(&[(0, 1, 1.5), (1, 0, -0.5), (1, 2, 2.), (2, 2, 0.)]);
+    let h = g.map(|n| n, |_, _, w| w.to_string());
+    assert_eq!(h.node_count(), g.node_count());
+    assert_eq!(h.edge_count(), g.edge_count());
+    for (a, b, w) in g.all_edges() {
+        assert_eq!(h[(a, b)], w.to_string());
+    }
+    assert!(h.nodes().eq(g.nodes()));
+    assert_eq!(h.check_invariants(), Ok(()));
+
+    let h = g.filter_map(Some, |_, _, &w| if w > 0. { Some(w) } else { None });
+    assert_eq!(h.node_count(), 3);
+    assert_eq!(h.edge_count(), 2);
+    assert!(!h.contains_edge(1, 0));
+    assert!(h.contains_edge(0, 1));
+    assert_eq!(h.neighbors_directed(0, Incoming).count(), 0);
+    assert_eq!(h.check_invariants(), Ok(()));
+
+    let h = g.filter_map(|n| if n != 1 { Some(n) } else { None }, |_, _, &w| Some(w));
+    assert_eq!(h.nodes().collect::<Vec<_>>(), vec![0, 2]);
+    assert_eq!(h.edge_count(), 1);
+    assert_eq!(h.check_invariants(), Ok(()));
+
+    // rekeying nodes keeps the structure and the order
+    let h = g.map(|n| (n as u8 + b'a') as char, |a, b, _| (a, b));
+    assert_eq!(h.nodes().collect::<Vec<_>>(), vec!['a', 'b', 'c']);
+    assert_eq!(h[('b', 'a')], (1, 0));
+    assert_eq!(h[('c', 'c')], (2, 2));
+    assert_eq!(h.edge_count(), g.edge_count());
+    assert_eq!(h.check_invariants(), Ok(()));
+
+    let h = g.filter_map(|n| if n != 0 { Some(-n) } else { None }, |_, _, &w| Some(w));
+    assert_eq!(h.nodes().collect::<Vec<_>>(), vec![-1, -2]);
+    assert!(h.contains_edge(-1, -2) && h.contains_edge(-2, -2));
+    assert_eq!(h.edge_count(), 2);
+    assert_eq!(h.check_invariants(), Ok(()));
+
+    // undirected edges are canonicalized under the new keys
+    let u = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2)]);
+    let v = u.map(|n| 2 - n, |_, _, _| ());
+    assert!(v.contains_edge(2, 1) && v.contains_edge(0, 1));
+    assert_eq!(v.check_invariants(), Ok(()));
+}
+
+#[test]
+#[should_panic(expected = "same label")]
+fn map_collision() {
+    let g = DiGraphMap::<u32, ()>::from_edges(&[(1, 2), (2, 3)]);
+    g.map(|n| n / 2, |_, _, _| ());
+}
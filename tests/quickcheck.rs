@@ -28,7 +28,6 @@ use petgraph::algo::{
 use petgraph::data::FromElements;
 use petgraph::dot::{Config, Dot};
 use petgraph::graph::{edge_index, node_index, IndexType};
-use petgraph::graphmap::NodeTrait;
 use petgraph::operator::complement;
 use petgraph::prelude::*;
 use petgraph::visit::{
@@ -47,8 +46,6 @@ where
     Graph::from_elements(min_spanning_tree(&g))
 }
 
-use std::fmt;
-
 quickcheck! {
     fn mst_directed(g: Small<Graph<(), u32>>) -> bool {
         // filter out isolated nodes
@@ -416,40 +413,11 @@ fn stable_graph_add_remove_edges() {
     quickcheck::quickcheck(prop as fn(StableGraph<_, _, Directed>, _) -> bool);
 }
 
-fn assert_graphmap_consistent<N, E, Ty>(g: &GraphMap<N, E, Ty>)
-where
-    Ty: EdgeType,
-    N: NodeTrait + fmt::Debug,
-{
-    for (a, b, _weight) in g.all_edges() {
-        assert!(
-            g.contains_edge(a, b),
-            "Edge not in graph! {:?} to {:?}",
-            a,
-            b
-        );
-        assert!(
-            g.neighbors(a).find(|x| *x == b).is_some(),
-            "Edge {:?} not in neighbor list for {:?}",
-            (a, b),
-            a
-        );
-        if !g.is_directed() {
-            assert!(
-                g.neighbors(b).find(|x| *x == a).is_some(),
-                "Edge {:?} not in neighbor list for {:?}",
-                (b, a),
-                b
-            );
-        }
-    }
-}
-
 #[test]
 fn graphmap_remove() {
     fn prop<Ty: EdgeType>(mut g: GraphMap<i8, (), Ty>, a: i8, b: i8) -> bool {
         //if g.edge_count() > 20 { return true; }
-        assert_graphmap_consistent(&g);
+        assert_eq!(g.check_invariants(), Ok(()));
         let contains = g.contains_edge(a, b);
         if !g.is_directed() {
             assert_eq!(contains, g.contains_edge(b, a));
@@ -458,7 +426,7 @@ fn graphmap_remove() {
         assert!(!g.contains_edge(a, b) && g.neighbors(a).find(|x| *x == b).is_none());
         //(g.is_directed() || g.neighbors(b).find(|x| *x == a).is_none()));
         assert!(g.remove_edge(a, b).is_none());
-        assert_graphmap_consistent(&g);
+        assert_eq!(g.check_invariants(), Ok(()));
         true
     }
     quickcheck::quickcheck(prop as fn(DiGraphMap<_, _>, _, _) -> bool);
@@ -477,6 +445,93 @@ fn graphmap_add_remove() {
     quickcheck::quickcheck(prop as fn(_, _, _) -> bool);
 }
 
+#[test]
+fn graphmap_self_loop_churn() {
+    // Interleave self loops with ordinary edges and node removals; the
+    // adjacency lists and the edge map must agree after every step.
+    fn prop(ops: Vec<(u8, i8, i8)>) -> bool {
+        let mut g = DiGraphMap::<i8, ()>::new();
+        for (op, a, b) in ops {
+            let a = a % 8;
+            let b = b % 8;
+            match op % 4 {
+                0 => {
+                    g.add_edge(a, a, ());
+                }
+                1 => {
+                    g.add_edge(a, b, ());
+                }
+                2 => {
+                    let contains = g.contains_edge(a, a);
+                    assert_eq!(g.remove_edge(a, a).is_some(), contains);
+                    assert!(!g.contains_edge(a, a));
+                }
+                _ => {
+                    g.remove_node(a);
+                }
+            }
+            assert_eq!(g.check_invariants(), Ok(()));
+            let adjacencies: usize = g.nodes().map(|n| g.neighbors(n).count()).sum();
+            assert_eq!(adjacencies, g.edge_count());
+            for n in g.nodes() {
+                assert_eq!(g.contains_edge(n, n), g.neighbors(n).any(|m| m == n));
+            }
+        }
+        true
+    }
+    quickcheck::quickcheck(prop as fn(_) -> bool);
+}
+
+#[test]
+fn graphmap_eq_insertion_order() {
+    fn prop<Ty: EdgeType>(g: GraphMap<i8, i8, Ty>) -> bool {
+        let mut rng = rand::thread_rng();
+        let mut nodes = g.nodes().collect::<Vec<_>>();
+        let mut edges = g.all_edges().collect::<Vec<_>>();
+        rng.shuffle(&mut nodes);
+        rng.shuffle(&mut edges);
+        let mut ng = GraphMap::<i8, i8, Ty>::new();
+        for n in nodes {
+            ng.add_node(n);
+        }
+        for (a, b, &w) in edges {
+            // undirected edges may be added in either orientation
+            if !g.is_directed() && rng.gen() {
+                ng.add_edge(b, a, w);
+            } else {
+                ng.add_edge(a, b, w);
+            }
+        }
+        assert!(g == ng);
+        if let Some((a, b, &w)) = g.all_edges().next() {
+            ng[(a, b)] = w.wrapping_add(1);
+            assert!(g != ng);
+        }
+        true
+    }
+    quickcheck::quickcheck(prop as fn(DiGraphMap<_, _>) -> bool);
+    quickcheck::quickcheck(prop as fn(UnGraphMap<_, _>) -> bool);
+}
+
+#[test]
+fn graphmap_invariants() {
+    fn prop<Ty: EdgeType>(mut g: GraphMap<i8, (), Ty>, removals: Vec<(i8, i8)>) -> bool {
+        assert_eq!(g.check_invariants(), Ok(()));
+        for (a, b) in removals {
+            if a % 2 == 0 {
+                g.remove_node(a);
+            } else {
+                g.remove_edge(a, b);
+            }
+            assert_eq!(g.check_invariants(), Ok(()));
+        }
+        g.reverse();
+        g.check_invariants() == Ok(())
+    }
+    quickcheck::quickcheck(prop as fn(DiGraphMap<_, _>, _) -> bool);
+    quickcheck::quickcheck(prop as fn(UnGraphMap<_, _>, _) -> bool);
+}
+
 fn sort_sccs<T: Ord>(v: &mut [Vec<T>]) {
     for scc in &mut *v {
         scc.sort();